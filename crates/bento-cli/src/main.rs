@@ -4,12 +4,141 @@ use clap::{Parser, Subcommand, ValueHint};
 use libbento::{
     cgroups::CgroupsConfig,
     process::{
-        Config, RootfsPopulationMethod, create_container, delete_container, load_container_state,
-        start_container, stop_container,
+        Config, RootfsPopulationMethod, attach, create_container, delete_container,
+        exec_container, logs, pause_container, resume_container, signal_container,
+        start_container, state, stop_container,
     },
 };
 use log::info;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Default capability set granted to the container process, matching the
+/// set `runc spec` grants by default (the usual Docker default list).
+const DEFAULT_CAPABILITIES: &[&str] = &[
+    "CAP_AUDIT_WRITE",
+    "CAP_KILL",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_MKNOD",
+    "CAP_NET_RAW",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETFCAP",
+    "CAP_SETPCAP",
+    "CAP_SYS_CHROOT",
+];
+
+/// Writes a `runc spec`-style `config.json` into `bundle_dir`, matching the
+/// field names `libbento::config::Config` actually parses (e.g.
+/// `no_new_privileges`, `container_id`/`host_id` id mappings, `net`/`mnt`
+/// namespace types) so a generated bundle can round-trip through `bento
+/// create`. Returns the path written.
+fn generate_spec(bundle_dir: &Path, rootless: bool) -> anyhow::Result<PathBuf> {
+    std::fs::create_dir_all(bundle_dir)?;
+
+    let capabilities = serde_json::json!({
+        "bounding": DEFAULT_CAPABILITIES,
+        "effective": DEFAULT_CAPABILITIES,
+        "inheritable": DEFAULT_CAPABILITIES,
+        "permitted": DEFAULT_CAPABILITIES,
+        "ambient": DEFAULT_CAPABILITIES,
+    });
+
+    let mut namespaces = vec![
+        serde_json::json!({"type": "pid"}),
+        serde_json::json!({"type": "ipc"}),
+        serde_json::json!({"type": "uts"}),
+        serde_json::json!({"type": "mnt"}),
+    ];
+
+    if rootless {
+        namespaces.push(serde_json::json!({"type": "user"}));
+    }
+
+    // `Config::deserialize` (config.rs) always runs its rootless validation
+    // and requires a uid/gid 0 mapping regardless of this flag, so the
+    // mappings are unconditional; `--rootless` only adds the user namespace.
+    let uid = nix::unistd::Uid::current().as_raw();
+    let gid = nix::unistd::Gid::current().as_raw();
+    let linux = serde_json::json!({
+        "namespaces": namespaces,
+        "maskedPaths": libbento::fs::DEFAULT_MASKED_PATHS,
+        "readonlyPaths": libbento::fs::DEFAULT_READONLY_PATHS,
+        "uidMappings": [
+            {"container_id": 0, "host_id": uid, "size": 1}
+        ],
+        "gidMappings": [
+            {"container_id": 0, "host_id": gid, "size": 1}
+        ],
+    });
+
+    let spec = serde_json::json!({
+        "ociVersion": "1.0.2",
+        "process": {
+            "args": ["sh"],
+            "env": [
+                "PATH=/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin",
+                "TERM=xterm"
+            ],
+            "cwd": "/",
+            "no_new_privileges": true,
+            "capabilities": capabilities,
+        },
+        "root": {
+            "path": "rootfs",
+            "readonly": false,
+        },
+        "hostname": "bento-container",
+        "mounts": [
+            {"destination": "/proc", "type": "proc", "source": "proc", "options": []},
+            {
+                "destination": "/dev",
+                "type": "tmpfs",
+                "source": "tmpfs",
+                "options": ["nosuid", "strictatime", "mode=755", "size=65536k"]
+            },
+            {
+                "destination": "/dev/pts",
+                "type": "devpts",
+                "source": "devpts",
+                "options": ["nosuid", "noexec", "newinstance", "ptmxmode=0666", "mode=0620", "gid=5"]
+            },
+            {
+                "destination": "/dev/shm",
+                "type": "tmpfs",
+                "source": "shm",
+                "options": ["nosuid", "noexec", "nodev", "mode=1777", "size=65536k"]
+            },
+            {
+                "destination": "/dev/mqueue",
+                "type": "mqueue",
+                "source": "mqueue",
+                "options": ["nosuid", "noexec", "nodev"]
+            },
+            {
+                "destination": "/sys",
+                "type": "sysfs",
+                "source": "sysfs",
+                "options": ["nosuid", "noexec", "nodev", "ro"]
+            },
+            {
+                "destination": "/sys/fs/cgroup",
+                "type": "cgroup",
+                "source": "cgroup",
+                "options": ["nosuid", "noexec", "nodev", "relatime", "ro"]
+            },
+        ],
+        "linux": linux,
+    });
+
+    let config_path = bundle_dir.join("config.json");
+    std::fs::write(&config_path, serde_json::to_string_pretty(&spec)?)?;
+
+    Ok(config_path)
+}
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -21,7 +150,15 @@ struct Cli {
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
-    Spec {},
+    Spec {
+        /// Directory to write config.json into (defaults to the current directory)
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        bundle: Option<PathBuf>,
+
+        /// Also emit a user namespace (the uid/gid mapping for the current user is always emitted)
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        rootless: bool,
+    },
     Create {
         #[arg(required = true)]
         container_id: String,
@@ -56,6 +193,18 @@ pub enum Commands {
 
         #[arg(long, action = clap::ArgAction::SetTrue)]
         no_cgroups: bool,
+
+        /// Networking backend: 'none' (default), 'slirp' (per-container
+        /// namespace via slirp4netns), or 'veth' (persistent address on the
+        /// shared bento0 bridge)
+        #[arg(long, default_value = "none")]
+        network: String,
+
+        /// Forward a host port into the container, as
+        /// [bind_addr:]host_port:container_port[/proto] (repeatable); only
+        /// takes effect with `--network slirp`
+        #[arg(short = 'p', long = "publish")]
+        publish: Vec<String>,
     },
     Start {
         #[arg(required = true)]
@@ -69,15 +218,66 @@ pub enum Commands {
     Kill {
         #[arg(required = true)]
         container_id: String,
+
+        /// Signal to send to the container's init process (e.g. TERM, KILL, SIGUSR1, or numeric)
+        signal: Option<String>,
+
+        /// Fully stop the container (wait for exit, reap, update state) instead
+        /// of just forwarding the signal; defaults to SIGKILL if no signal is given
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        all: bool,
     },
     Delete {
         #[arg(required = true)]
         container_id: String,
     },
+    Pause {
+        #[arg(required = true)]
+        container_id: String,
+    },
+    Resume {
+        #[arg(required = true)]
+        container_id: String,
+    },
+    Exec {
+        #[arg(required = true)]
+        container_id: String,
+
+        /// Allocate a pty and attach it to the exec'd process as its
+        /// controlling terminal
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        tty: bool,
+
+        /// Extra environment variable to set, as KEY=value (repeatable)
+        #[arg(short, long)]
+        env: Vec<String>,
+
+        /// Command (and arguments) to run inside the running container
+        #[arg(required = true, trailing_var_arg = true)]
+        command: Vec<String>,
+    },
+    Logs {
+        #[arg(required = true)]
+        container_id: String,
+
+        /// Keep streaming new output as it's written (like `tail -f`)
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        follow: bool,
+    },
+    Attach {
+        #[arg(required = true)]
+        container_id: String,
+    },
     Stats {
         #[arg(short, long, action = clap::ArgAction::SetTrue)]
         continuous: bool,
     },
+    /// Run a remote management daemon exposing a Docker-style REST API
+    /// over a UNIX socket
+    Daemon {
+        #[arg(short, long, default_value = "/run/bento.sock")]
+        socket: PathBuf,
+    },
 }
 
 fn main() {
@@ -86,8 +286,15 @@ fn main() {
     let args = Cli::parse();
 
     match args.command {
-        Commands::Spec {} => {
-            todo!("Generate OCI spec template");
+        Commands::Spec { bundle, rootless } => {
+            let bundle_dir = bundle.unwrap_or_else(|| PathBuf::from("."));
+            match generate_spec(&bundle_dir, rootless) {
+                Ok(path) => println!("Generated spec: {}", path.display()),
+                Err(e) => {
+                    eprintln!("Failed to generate spec: {e}");
+                    std::process::exit(1);
+                }
+            }
         }
         Commands::Create {
             container_id,
@@ -100,6 +307,8 @@ fn main() {
             memory_swap_limit,
             pids_limit,
             no_cgroups,
+            network,
+            publish,
         } => {
             println!(
                 "Creating container '{}' with bundle '{}' using {} method",
@@ -108,20 +317,34 @@ fn main() {
                 population_method
             );
 
-            let mut config = Config {
-                container_id: container_id.clone(),
-                bundle_path: bundle.to_string_lossy().to_string(),
-                population_method: match population_method.as_str() {
-                    "manual" => RootfsPopulationMethod::Manual,
-                    _ => RootfsPopulationMethod::BusyBox, // Clear default handling
-                },
-                ..Config::default() // Use default for remaining fields
+            let bundle_str = bundle.to_string_lossy().to_string();
+            let mut config = match Config::from_bundle(&bundle_str, &container_id) {
+                Ok(config) => {
+                    println!("Loaded OCI config.json from bundle '{}'", bundle_str);
+                    config
+                }
+                Err(e) => {
+                    println!("No usable config.json in bundle ({e}), using built-in demo command");
+                    Config {
+                        container_id: container_id.clone(),
+                        bundle_path: bundle_str,
+                        ..Config::default()
+                    }
+                }
+            };
+
+            config.population_method = match population_method.as_str() {
+                "manual" => RootfsPopulationMethod::Manual,
+                _ => RootfsPopulationMethod::BusyBox, // Clear default handling
             };
 
             if no_cgroups {
                 config.cgroups = CgroupsConfig::default();
             } else {
-                let mut cgroups_config = CgroupsConfig::new();
+                // Start from whatever `linux.resources` the bundle already
+                // populated `config.cgroups` with, then let the explicit
+                // flags below override individual knobs.
+                let mut cgroups_config = config.cgroups.clone();
 
                 if let Some(memory) = memory_limit {
                     cgroups_config.memory_max = Some(memory.clone());
@@ -150,6 +373,16 @@ fn main() {
                 config.cgroups = cgroups_config;
             }
 
+            config.network_mode = match network.as_str() {
+                "slirp" => libbento::networking::NetworkMode::Slirp4netns,
+                "veth" => libbento::networking::NetworkMode::Veth,
+                _ => libbento::networking::NetworkMode::None,
+            };
+            config.port_mappings = publish
+                .iter()
+                .flat_map(|p| libbento::networking::parse_port_mappings(p))
+                .collect();
+
             match create_container(&config) {
                 Ok(_) => println!("Container '{container_id}' created successfully"),
                 Err(e) => {
@@ -169,23 +402,9 @@ fn main() {
             }
         }
         Commands::State { container_id } => {
-            println!("State of container '{container_id}'");
-            match load_container_state(&container_id) {
-                Ok(state) => {
-                    println!("Container ID: {}", state.id);
-                    println!("Status: {}", state.status);
-                    println!("PID: {}", state.pid);
-                    println!("Bundle Path: {}", state.bundle_path);
-                    println!("Created At: {}", state.created_at);
-                    println!("Cgroups Enabled: {}", state.cgroup_enabled);
-                    if let Some(pipe_path) = &state.start_pipe_path {
-                        println!("Start Pipe Path: {}", pipe_path);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Failed to get state for container '{container_id}': {e}");
-                    std::process::exit(1);
-                }
+            if let Err(e) = state(&container_id) {
+                eprintln!("Failed to get state for container '{container_id}': {e}");
+                std::process::exit(1);
             }
         }
         Commands::List {} => {
@@ -220,12 +439,26 @@ fn main() {
                 }
             }
         }
-        Commands::Kill { container_id } => {
-            println!("Killing container '{container_id}'");
-            match stop_container(&container_id) {
-                Ok(_) => println!("Container '{container_id}' stopped successfully"),
-                Err(e) => {
-                    eprintln!("Failed to stop container '{container_id}': {e}");
+        Commands::Kill {
+            container_id,
+            signal,
+            all,
+        } => {
+            if all {
+                let signal = signal.unwrap_or_else(|| "KILL".to_string());
+                println!("Stopping container '{container_id}' (signal {signal})");
+                match stop_container(&container_id, Some(&signal)) {
+                    Ok(_) => println!("Container '{container_id}' stopped successfully"),
+                    Err(e) => {
+                        eprintln!("Failed to stop container '{container_id}': {e}");
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                let signal = signal.unwrap_or_else(|| "TERM".to_string());
+                println!("Sending {signal} to container '{container_id}'");
+                if let Err(e) = signal_container(&container_id, &signal) {
+                    eprintln!("Failed to signal container '{container_id}': {e}");
                     std::process::exit(1);
                 }
             }
@@ -240,6 +473,53 @@ fn main() {
                 }
             }
         }
+        Commands::Pause { container_id } => {
+            println!("Pausing container '{container_id}'");
+            match pause_container(&container_id) {
+                Ok(_) => println!("Container '{container_id}' paused successfully"),
+                Err(e) => {
+                    eprintln!("Failed to pause container '{container_id}': {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Resume { container_id } => {
+            println!("Resuming container '{container_id}'");
+            match resume_container(&container_id) {
+                Ok(_) => println!("Container '{container_id}' resumed successfully"),
+                Err(e) => {
+                    eprintln!("Failed to resume container '{container_id}': {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Commands::Exec {
+            container_id,
+            tty,
+            env,
+            command,
+        } => {
+            println!("Executing {command:?} in container '{container_id}'");
+            if let Err(e) = exec_container(&container_id, &command, &env, tty) {
+                eprintln!("Failed to exec in container '{container_id}': {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Logs {
+            container_id,
+            follow,
+        } => {
+            if let Err(e) = logs(&container_id, follow) {
+                eprintln!("Failed to read logs for container '{container_id}': {e}");
+                std::process::exit(1);
+            }
+        }
+        Commands::Attach { container_id } => {
+            if let Err(e) = attach(&container_id) {
+                eprintln!("Failed to attach to container '{container_id}': {e}");
+                std::process::exit(1);
+            }
+        }
         Commands::Stats { continuous } => {
             loop {
                 // Clear screen
@@ -284,5 +564,12 @@ fn main() {
                 std::thread::sleep(std::time::Duration::from_secs(2));
             }
         }
+        Commands::Daemon { socket } => {
+            println!("Starting bento daemon on {}", socket.display());
+            if let Err(e) = libbento::api::serve(&socket) {
+                eprintln!("Daemon failed: {e}");
+                std::process::exit(1);
+            }
+        }
     }
 }
@@ -0,0 +1,304 @@
+// Minimal Docker-style REST control plane served over a UNIX socket, so
+// external tooling can drive bento without shelling out to the CLI. Hand-rolls
+// just enough of HTTP/1.1 to carry a handful of small JSON bodies - the same
+// shape as the slirp4netns API-socket client in `networking.rs` and the
+// container notify socket: a small hand-rolled protocol over a UNIX socket
+// rather than pulling in a web framework.
+
+use crate::process::{self, Config, RootfsPopulationMethod};
+use anyhow::{Context, Result, anyhow};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// Binds `socket_path` (removing a stale socket left by a previous run) and
+/// serves requests, one thread per connection, until the process is killed.
+pub fn serve(socket_path: &Path) -> Result<()> {
+    if socket_path.exists() {
+        std::fs::remove_file(socket_path)
+            .with_context(|| format!("Failed to remove stale socket {}", socket_path.display()))?;
+    }
+
+    let listener = UnixListener::bind(socket_path)
+        .with_context(|| format!("Failed to bind API socket {}", socket_path.display()))?;
+    println!("[Daemon] Listening on {}", socket_path.display());
+
+    for conn in listener.incoming() {
+        match conn {
+            Ok(stream) => {
+                thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream) {
+                        eprintln!("[Daemon] Connection error: {e}");
+                    }
+                });
+            }
+            Err(e) => eprintln!("[Daemon] Failed to accept connection: {e}"),
+        }
+    }
+    Ok(())
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+fn handle_connection(stream: UnixStream) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let request = read_request(&mut reader)?;
+    let mut stream = reader.into_inner();
+    route(&request, &mut stream)
+}
+
+/// Reads just enough of an HTTP/1.1 request - the request line, headers up
+/// to the blank line, and a `Content-Length`-sized body - to dispatch it.
+/// Chunked request bodies aren't supported; none of our endpoints need them.
+fn read_request(reader: &mut BufReader<UnixStream>) -> Result<Request> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts
+        .next()
+        .ok_or_else(|| anyhow!("Malformed request line"))?
+        .to_string();
+    let path = parts
+        .next()
+        .ok_or_else(|| anyhow!("Malformed request line"))?
+        .to_string();
+
+    let mut content_length = 0usize;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request { method, path, body })
+}
+
+fn write_json(stream: &mut UnixStream, status: u16, body: &serde_json::Value) -> Result<()> {
+    let status_text = match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    };
+    let payload = serde_json::to_vec(body)?;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {status_text}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        payload.len()
+    )?;
+    stream.write_all(&payload)?;
+    Ok(())
+}
+
+fn error_response(stream: &mut UnixStream, status: u16, message: impl std::fmt::Display) -> Result<()> {
+    write_json(stream, status, &serde_json::json!({ "message": message.to_string() }))
+}
+
+fn route(request: &Request, stream: &mut UnixStream) -> Result<()> {
+    let segments: Vec<&str> = request
+        .path
+        .trim_start_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["containers", "create"]) => create(request, stream),
+        ("POST", ["containers", id, "start"]) => start(id, stream),
+        ("GET", ["containers", "json"]) => list(stream),
+        ("GET", ["containers", id, "json"]) => inspect(id, stream),
+        ("POST", ["containers", id, "kill"]) => kill(request, id, stream),
+        ("DELETE", ["containers", id]) => delete(id, stream),
+        ("GET", ["containers", id, "stats"]) => stats(id, stream),
+        _ => error_response(stream, 404, "No such route"),
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct CreateRequest {
+    container_id: String,
+    bundle: String,
+    #[serde(default)]
+    population_method: Option<String>,
+}
+
+fn create(request: &Request, stream: &mut UnixStream) -> Result<()> {
+    let body: CreateRequest = match serde_json::from_slice(&request.body) {
+        Ok(body) => body,
+        Err(e) => return error_response(stream, 400, format!("Invalid request body: {e}")),
+    };
+
+    let mut config = match Config::from_bundle(&body.bundle, &body.container_id) {
+        Ok(config) => config,
+        Err(e) => return error_response(stream, 400, format!("Failed to load bundle config: {e}")),
+    };
+    if let Some(method) = body.population_method.as_deref() {
+        config.population_method = match method {
+            "manual" => RootfsPopulationMethod::Manual,
+            _ => RootfsPopulationMethod::BusyBox,
+        };
+    }
+
+    match process::create_container(&config) {
+        Ok(_) => write_json(stream, 201, &serde_json::json!({ "Id": body.container_id })),
+        Err(e) => error_response(stream, 500, e),
+    }
+}
+
+/// Rejects a path-segment `container_id` before it reaches any handler, the
+/// same restriction `process::validate_container_id` applies everywhere else
+/// - the daemon is a local-socket client boundary, so an id like
+/// `"../../../etc/cron.d/x"` must be caught here too, not just deep inside
+/// the process/fs helpers it would eventually reach.
+fn require_valid_id(id: &str, stream: &mut UnixStream) -> Result<bool> {
+    if let Err(e) = process::validate_container_id(id) {
+        error_response(stream, 400, e)?;
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn start(id: &str, stream: &mut UnixStream) -> Result<()> {
+    if !require_valid_id(id, stream)? {
+        return Ok(());
+    }
+    match process::start_container(id) {
+        Ok(_) => write_json(stream, 204, &serde_json::json!({})),
+        Err(e) => error_response(stream, 500, e),
+    }
+}
+
+fn list(stream: &mut UnixStream) -> Result<()> {
+    match process::list_containers() {
+        Ok(containers) => {
+            let body: Vec<_> = containers
+                .iter()
+                .map(|c| {
+                    serde_json::json!({
+                        "Id": c.id,
+                        "Status": c.display_status(),
+                        "Pid": c.pid,
+                        "BundlePath": c.bundle_path,
+                        "Created": c.created_at,
+                    })
+                })
+                .collect();
+            write_json(stream, 200, &serde_json::Value::Array(body))
+        }
+        Err(e) => error_response(stream, 500, e),
+    }
+}
+
+fn inspect(id: &str, stream: &mut UnixStream) -> Result<()> {
+    if !require_valid_id(id, stream)? {
+        return Ok(());
+    }
+    match process::container_oci_state(id) {
+        Ok(oci_state) => write_json(stream, 200, &serde_json::to_value(oci_state)?),
+        Err(e) => error_response(stream, 404, e),
+    }
+}
+
+#[derive(serde::Deserialize, Default)]
+struct KillRequest {
+    signal: Option<String>,
+}
+
+fn kill(request: &Request, id: &str, stream: &mut UnixStream) -> Result<()> {
+    if !require_valid_id(id, stream)? {
+        return Ok(());
+    }
+    let body: KillRequest = if request.body.is_empty() {
+        KillRequest::default()
+    } else {
+        serde_json::from_slice(&request.body).unwrap_or_default()
+    };
+    let signal = body.signal.as_deref().unwrap_or("KILL");
+
+    match process::stop_container(id, Some(signal)) {
+        Ok(_) => write_json(stream, 204, &serde_json::json!({})),
+        Err(e) => error_response(stream, 500, e),
+    }
+}
+
+fn delete(id: &str, stream: &mut UnixStream) -> Result<()> {
+    if !require_valid_id(id, stream)? {
+        return Ok(());
+    }
+    match process::delete_container(id) {
+        Ok(_) => write_json(stream, 204, &serde_json::json!({})),
+        Err(e) => error_response(stream, 500, e),
+    }
+}
+
+/// Streams the same per-container memory/CPU/pids numbers `bento stats`
+/// prints, as one chunked JSON object per ~2 second interval, until the
+/// client disconnects.
+fn stats(id: &str, stream: &mut UnixStream) -> Result<()> {
+    if !require_valid_id(id, stream)? {
+        return Ok(());
+    }
+
+    // Validate the container exists before committing to the chunked
+    // response: once the `200` header below is written, there's no way to
+    // report a failure except inside the chunked body itself, which isn't
+    // valid HTTP framing for a clean error status.
+    let mut cgroup_stats = match process::cgroup_freezer_backend(id).and_then(|b| b.get_stats()) {
+        Ok(stats) => stats,
+        Err(e) => return error_response(stream, 404, e),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+    )?;
+
+    loop {
+        let mut line = serde_json::to_string(&serde_json::json!({
+            "id": id,
+            "memory_usage_bytes": cgroup_stats.memory_usage,
+            "memory_limit_bytes": cgroup_stats.memory_limit,
+            "cpu_usage_usec": cgroup_stats.cpu_usage_usec,
+            "pids_current": cgroup_stats.pids_current,
+            "pids_limit": cgroup_stats.pids_limit,
+        }))?;
+        line.push('\n');
+
+        let chunk = format!("{:x}\r\n{line}\r\n", line.len());
+        if stream.write_all(chunk.as_bytes()).is_err() {
+            break;
+        }
+
+        thread::sleep(Duration::from_secs(2));
+
+        // The `200` header is already committed to the wire, so a failure
+        // here (e.g. the container was deleted mid-stream) just ends the
+        // chunked response instead of trying to report a status code.
+        cgroup_stats = match process::cgroup_freezer_backend(id).and_then(|b| b.get_stats()) {
+            Ok(stats) => stats,
+            Err(_) => break,
+        };
+    }
+
+    let _ = stream.write_all(b"0\r\n\r\n");
+    Ok(())
+}
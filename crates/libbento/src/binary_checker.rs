@@ -28,6 +28,31 @@ impl BinaryChecker {
         Ok(())
     }
 
+    /// Binaries needed by [`crate::networking::setup_veth_network`]'s
+    /// bridge/veth/NAT path, as opposed to [`Self::validate_required_binaries`]'s
+    /// slirp4netns ones.
+    pub fn validate_veth_binaries() -> Result<()> {
+        if !Self::binary_exists("ip") {
+            return Err(anyhow!(
+                "ip not found. Install with: sudo apt-get install iproute2"
+            ));
+        }
+
+        if !Self::binary_exists("nsenter") {
+            return Err(anyhow!(
+                "nsenter not found. Install with: sudo apt-get install util-linux"
+            ));
+        }
+
+        if !Self::binary_exists("iptables") {
+            return Err(anyhow!(
+                "iptables not found. Install with: sudo apt-get install iptables"
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn check_system() -> Result<()> {
         println!("🔍 Checking system capabilities...\n");
 
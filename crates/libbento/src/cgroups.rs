@@ -1,7 +1,66 @@
 use anyhow::{Context, Result, anyhow};
+use dbus::arg::{RefArg, Variant};
+use dbus::blocking::Connection;
 use nix::unistd::Pid;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Which backend `CgroupManager` should use to apply limits.
+///
+/// `Fs` is bento's original behaviour: write directly into a delegated
+/// cgroup v2 subtree. `Systemd` instead asks systemd to create and own a
+/// transient scope unit, which avoids racing systemd for control of the
+/// hierarchy on hosts where systemd manages the user/session cgroups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum CgroupDriver {
+    #[default]
+    Fs,
+    Systemd,
+}
+
+/// Which cgroup hierarchy layout the host actually has mounted.
+///
+/// Returned by [`detect_cgroup_setup`] so `setup_cgroups` can pick the right
+/// [`CgroupBackend`] instead of assuming the unified v2 hierarchy every host
+/// has one, which isn't true on older kernels and some hybrid distros.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CgroupVersion {
+    V1,
+    V2,
+}
+
+/// Statfs-checks `/sys/fs/cgroup` to tell a unified cgroup v2 mount apart
+/// from the legacy tmpfs-of-per-controller-mounts v1 layout.
+pub fn detect_cgroup_setup() -> CgroupVersion {
+    const CGROUP2_SUPER_MAGIC: i64 = 0x6367_7270;
+
+    let path = match std::ffi::CString::new("/sys/fs/cgroup") {
+        Ok(p) => p,
+        Err(_) => return CgroupVersion::V1,
+    };
+
+    let mut buf: nix::libc::statfs = unsafe { std::mem::zeroed() };
+    let ret = unsafe { nix::libc::statfs(path.as_ptr(), &mut buf) };
+    if ret == 0 && buf.f_type as i64 == CGROUP2_SUPER_MAGIC {
+        CgroupVersion::V2
+    } else {
+        CgroupVersion::V1
+    }
+}
+
+/// Common surface both the cgroup v2 (`CgroupManager`) and cgroup v1
+/// (`CgroupManagerV1`) backends implement, so `setup_cgroups`/`cleanup_cgroups`
+/// can stay agnostic to which hierarchy layout the host actually has mounted.
+pub trait CgroupBackend {
+    fn setup(&self, config: &CgroupsConfig, pid: Pid) -> Result<()>;
+    fn apply_limits(&self, config: &CgroupsConfig) -> Result<()>;
+    fn add_process(&self, pid: Pid) -> Result<()>;
+    fn get_stats(&self) -> Result<CgroupStats>;
+    fn cleanup(&self) -> Result<()>;
+    fn freeze(&self, state: FreezerState) -> Result<()>;
+    fn cgroup_path(&self) -> PathBuf;
+}
 
 #[derive(Debug, Clone, Default)]
 pub struct CgroupsConfig {
@@ -11,6 +70,10 @@ pub struct CgroupsConfig {
     pub cpu_max: Option<String>,
     pub cpu_weight: Option<u32>,
     pub pids_max: Option<String>,
+    pub driver: CgroupDriver,
+    /// Device whitelist rules, enforced via the BPF device controller on v2
+    /// hosts. Empty means "don't touch device access" (no program attached).
+    pub devices: Vec<crate::config::DeviceRule>,
 }
 
 impl CgroupsConfig {
@@ -34,27 +97,174 @@ impl CgroupsConfig {
             ..Default::default()
         }
     }
+
+    /// Maps an OCI runtime-spec `linux.resources` block onto bento's cgroup
+    /// v2 knobs.
+    pub fn from_oci(resources: &crate::config::Resources) -> Self {
+        let mut config = Self::default();
+
+        if let Some(memory) = &resources.memory {
+            config.memory_max = memory.limit.map(|v| v.to_string());
+            config.memory_high = memory.reservation.map(|v| v.to_string());
+            config.memory_swap_max = memory.swap.map(|v| v.to_string());
+        }
+
+        if let Some(cpu) = &resources.cpu {
+            if let Some(quota) = cpu.quota {
+                let period = cpu.period.unwrap_or(100_000);
+                config.cpu_max = Some(format!("{} {}", quota, period));
+            }
+            config.cpu_weight = cpu.shares.map(shares_to_weight);
+        }
+
+        if let Some(pids) = &resources.pids {
+            config.pids_max = Some(pids.limit.to_string());
+        }
+
+        if !resources.devices.is_empty() {
+            // `compile_program` checks the *last* entry of this list first
+            // (OCI devices are last-match-wins), so the defaults have to come
+            // first here and the bundle's own rules last - that's what makes
+            // a bundle's specific allow rule take priority over our injected
+            // defaults, and the bundle's own later rules take priority over
+            // its own earlier ones, matching the spec.
+            config.devices = crate::devices::default_device_rules()
+                .into_iter()
+                .chain(resources.devices.iter().cloned())
+                .collect();
+        }
+
+        config
+    }
+}
+
+impl From<&crate::config::Resources> for CgroupsConfig {
+    fn from(resources: &crate::config::Resources) -> Self {
+        CgroupsConfig::from_oci(resources)
+    }
+}
+
+/// Converts a v1 `cpu.shares` value (range 2-262144) into the v2 `cpu.weight`
+/// range (1-10000) via the standard kernel conversion formula.
+fn shares_to_weight(shares: u64) -> u32 {
+    let shares = shares.clamp(2, 262144);
+    (1 + ((shares - 2) * 9999) / 262142) as u32
+}
+
+/// Parses a cgroup-style size string ("512M", "2G", "max") into bytes.
+///
+/// Accepts the usual `K`/`M`/`G`/`T` suffixes (binary, i.e. 1K = 1024) as
+/// well as a bare number of bytes. `"max"`/`"infinity"` are rejected here;
+/// callers that need to thread those through to systemd's `infinity`
+/// sentinel should check for them before calling this.
+pub fn parse_size_to_bytes(value: &str) -> Result<u64> {
+    let trimmed = value.trim();
+    if trimmed.eq_ignore_ascii_case("max") || trimmed.eq_ignore_ascii_case("infinity") {
+        return Err(anyhow!("'{}' has no finite byte value", trimmed));
+    }
+
+    let (digits, multiplier) = match trimmed.chars().last() {
+        Some('K') | Some('k') => (&trimmed[..trimmed.len() - 1], 1024u64),
+        Some('M') | Some('m') => (&trimmed[..trimmed.len() - 1], 1024 * 1024),
+        Some('G') | Some('g') => (&trimmed[..trimmed.len() - 1], 1024 * 1024 * 1024),
+        Some('T') | Some('t') => (&trimmed[..trimmed.len() - 1], 1024 * 1024 * 1024 * 1024),
+        _ => (trimmed, 1u64),
+    };
+
+    let base: u64 = digits
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid size value: {}", value))?;
+
+    base.checked_mul(multiplier)
+        .ok_or_else(|| anyhow!("Size value overflows u64: {}", value))
+}
+
+/// Splits a cgroup v2 `cpu.max` string ("<quota> <period>" or "max <period>")
+/// into `(quota_us, period_us)`, defaulting the period to 100ms as the kernel
+/// does when it is omitted.
+fn parse_cpu_max(cpu_max: &str) -> Result<(Option<u64>, u64)> {
+    let mut parts = cpu_max.split_whitespace();
+    let quota_part = parts
+        .next()
+        .ok_or_else(|| anyhow!("Empty cpu_max value"))?;
+    let period_us: u64 = match parts.next() {
+        Some(period) => period
+            .parse()
+            .with_context(|| format!("Invalid cpu.max period: {}", period))?,
+        None => 100_000,
+    };
+
+    let quota_us = if quota_part.eq_ignore_ascii_case("max") {
+        None
+    } else {
+        Some(
+            quota_part
+                .parse()
+                .with_context(|| format!("Invalid cpu.max quota: {}", quota_part))?,
+        )
+    };
+
+    Ok((quota_us, period_us))
 }
 
 pub struct CgroupManager {
     container_id: String,
-    cgroup_path: PathBuf,
+    cgroup_path: std::cell::RefCell<PathBuf>,
     base_path: PathBuf,
+    driver: CgroupDriver,
+    device_prog_fd: std::cell::RefCell<Option<std::os::unix::io::RawFd>>,
 }
 
 impl CgroupManager {
     pub fn new(container_id: String) -> Result<Self> {
-        let base_path = get_user_cgroup_base()?;
-        let cgroup_path = base_path.join(&container_id);
+        Self::with_driver(container_id, CgroupDriver::Fs)
+    }
 
-        Ok(Self {
-            container_id,
-            cgroup_path,
-            base_path,
-        })
+    pub fn with_driver(container_id: String, driver: CgroupDriver) -> Result<Self> {
+        match driver {
+            CgroupDriver::Fs => {
+                let base_path = get_user_cgroup_base()?;
+                let cgroup_path = base_path.join(&container_id);
+
+                Ok(Self {
+                    container_id,
+                    cgroup_path: std::cell::RefCell::new(cgroup_path),
+                    base_path,
+                    driver,
+                    device_prog_fd: std::cell::RefCell::new(None),
+                })
+            }
+            CgroupDriver::Systemd => {
+                // The scope doesn't exist yet, so there's nothing to resolve;
+                // `setup` fills in `cgroup_path` once systemd creates the unit.
+                Ok(Self {
+                    container_id,
+                    cgroup_path: std::cell::RefCell::new(PathBuf::new()),
+                    base_path: PathBuf::from("/sys/fs/cgroup"),
+                    driver,
+                    device_prog_fd: std::cell::RefCell::new(None),
+                })
+            }
+        }
+    }
+
+    fn cgroup_path(&self) -> PathBuf {
+        self.cgroup_path.borrow().clone()
+    }
+
+    fn scope_name(&self) -> String {
+        format!("bento-{}.scope", self.container_id)
     }
 
     pub fn setup(&self, config: &CgroupsConfig, pid: Pid) -> Result<()> {
+        match self.driver {
+            CgroupDriver::Fs => self.setup_fs(config, pid),
+            CgroupDriver::Systemd => self.setup_systemd(config, pid),
+        }
+    }
+
+    fn setup_fs(&self, config: &CgroupsConfig, pid: Pid) -> Result<()> {
         println!(
             "[Cgroups] Setting up cgroups for container: {}",
             self.container_id
@@ -68,29 +278,144 @@ impl CgroupManager {
 
         self.add_process(pid)?;
 
+        self.attach_device_rules(config)?;
+
         println!("[Cgroups] Successfully configured cgroups for PID: {}", pid);
         Ok(())
     }
 
+    /// Attaches the BPF device whitelist program if `config` carries any
+    /// device rules. A no-op when the container didn't ask for device
+    /// filtering, so hosts without BPF support aren't affected by default.
+    fn attach_device_rules(&self, config: &CgroupsConfig) -> Result<()> {
+        if config.devices.is_empty() {
+            return Ok(());
+        }
+
+        let fd = crate::devices::attach(&self.cgroup_path(), &config.devices)?;
+        *self.device_prog_fd.borrow_mut() = Some(fd);
+        Ok(())
+    }
+
+    /// Creates a `bento-<id>.scope` transient unit via `StartTransientUnit` and
+    /// resolves its cgroup path so the rest of `CgroupManager` (stats, cleanup)
+    /// can keep reading the usual `/sys/fs/cgroup/.../memory.current` files.
+    fn setup_systemd(&self, config: &CgroupsConfig, pid: Pid) -> Result<()> {
+        println!(
+            "[Cgroups] Setting up systemd scope for container: {}",
+            self.container_id
+        );
+
+        let conn = Connection::new_session()
+            .context("Failed to connect to the systemd session D-Bus")?;
+        let proxy = conn.with_proxy(
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            Duration::from_secs(5),
+        );
+
+        let mut properties: Vec<(&str, Variant<Box<dyn RefArg>>)> = vec![
+            (
+                "PIDs",
+                Variant(Box::new(vec![pid.as_raw() as u32]) as Box<dyn RefArg>),
+            ),
+            ("Delegate", Variant(Box::new(true) as Box<dyn RefArg>)),
+        ];
+
+        if let Some(memory_max) = &config.memory_max {
+            let bytes = if memory_max.eq_ignore_ascii_case("max") {
+                u64::MAX
+            } else {
+                parse_size_to_bytes(memory_max)?
+            };
+            properties.push(("MemoryMax", Variant(Box::new(bytes) as Box<dyn RefArg>)));
+        }
+
+        if let Some(cpu_max) = &config.cpu_max {
+            let (quota_us, period_us) = parse_cpu_max(cpu_max)?;
+            if let Some(quota_us) = quota_us {
+                let quota_per_sec_usec = quota_us * 1_000_000 / period_us;
+                properties.push((
+                    "CPUQuotaPerSecUSec",
+                    Variant(Box::new(quota_per_sec_usec) as Box<dyn RefArg>),
+                ));
+            }
+        } else if let Some(weight) = config.cpu_weight {
+            properties.push(("CPUWeight", Variant(Box::new(weight as u64) as Box<dyn RefArg>)));
+        }
+
+        if let Some(pids_max) = &config.pids_max {
+            let max = if pids_max.eq_ignore_ascii_case("max") {
+                u64::MAX
+            } else {
+                pids_max
+                    .parse()
+                    .with_context(|| format!("Invalid pids_max: {}", pids_max))?
+            };
+            properties.push(("TasksMax", Variant(Box::new(max) as Box<dyn RefArg>)));
+        }
+
+        let aux: Vec<(String, Vec<(String, Variant<Box<dyn RefArg>>)>)> = Vec::new();
+
+        let (_job,): (dbus::Path,) = proxy
+            .method_call(
+                "org.freedesktop.systemd1.Manager",
+                "StartTransientUnit",
+                (self.scope_name(), "fail".to_string(), properties, aux),
+            )
+            .context("StartTransientUnit failed")?;
+
+        println!(
+            "[Cgroups] Started transient unit {} for PID {}",
+            self.scope_name(),
+            pid
+        );
+
+        let resolved = self.resolve_scope_cgroup_path()?;
+        println!("[Cgroups] Resolved scope cgroup path: {}", resolved.display());
+        *self.cgroup_path.borrow_mut() = resolved;
+
+        self.attach_device_rules(config)?;
+
+        Ok(())
+    }
+
+    /// Resolves the cgroup path systemd assigned to our scope by scanning the
+    /// user/system slice tree for a directory named after the scope unit.
+    fn resolve_scope_cgroup_path(&self) -> Result<PathBuf> {
+        for candidate in [
+            PathBuf::from("/sys/fs/cgroup/user.slice"),
+            PathBuf::from("/sys/fs/cgroup"),
+        ] {
+            if let Some(found) = find_dir_named(&candidate, &self.scope_name(), 6) {
+                return Ok(found);
+            }
+        }
+        Err(anyhow!(
+            "Could not locate cgroup path for unit {}",
+            self.scope_name()
+        ))
+    }
+
     fn create_cgroup_directory(&self) -> Result<()> {
-        if self.cgroup_path.exists() {
+        if self.cgroup_path().exists() {
             println!(
                 "[Cgroups] Cleaning up existing cgroup: {}",
-                self.cgroup_path.display()
+                self.cgroup_path().display()
             );
             self.cleanup_internal()?;
         }
 
-        fs::create_dir_all(&self.cgroup_path).with_context(|| {
+        fs::create_dir_all(&self.cgroup_path()).with_context(|| {
             format!(
                 "Failed to create cgroup directory: {}",
-                self.cgroup_path.display()
+                self.cgroup_path().display()
             )
         })?;
 
         println!(
             "[Cgroups] Created cgroup directory: {}",
-            self.cgroup_path.display()
+            self.cgroup_path().display()
         );
         Ok(())
     }
@@ -194,7 +519,7 @@ impl CgroupManager {
     }
 
     fn add_process(&self, pid: Pid) -> Result<()> {
-        let procs_file = self.cgroup_path.join("cgroup.procs");
+        let procs_file = self.cgroup_path().join("cgroup.procs");
         fs::write(&procs_file, pid.to_string())
             .with_context(|| format!("Failed to add PID {} to cgroup", pid))?;
 
@@ -203,7 +528,7 @@ impl CgroupManager {
     }
 
     fn write_cgroup_file(&self, filename: &str, content: &str) -> Result<()> {
-        let file_path = self.cgroup_path.join(filename);
+        let file_path = self.cgroup_path().join(filename);
         fs::write(&file_path, content)
             .with_context(|| format!("Failed to write to {}: {}", file_path.display(), content))?;
 
@@ -214,17 +539,17 @@ impl CgroupManager {
     pub fn get_stats(&self) -> Result<CgroupStats> {
         let mut stats = CgroupStats::default();
 
-        if let Ok(content) = fs::read_to_string(self.cgroup_path.join("memory.current")) {
+        if let Ok(content) = fs::read_to_string(self.cgroup_path().join("memory.current")) {
             stats.memory_usage = content.trim().parse().unwrap_or(0);
         }
 
-        if let Ok(content) = fs::read_to_string(self.cgroup_path.join("memory.max")) {
+        if let Ok(content) = fs::read_to_string(self.cgroup_path().join("memory.max")) {
             if content.trim() != "max" {
                 stats.memory_limit = content.trim().parse().ok();
             }
         }
 
-        if let Ok(content) = fs::read_to_string(self.cgroup_path.join("cpu.stat")) {
+        if let Ok(content) = fs::read_to_string(self.cgroup_path().join("cpu.stat")) {
             for line in content.lines() {
                 if line.starts_with("usage_usec ") {
                     if let Ok(usage) = line.split_whitespace().nth(1).unwrap_or("0").parse::<u64>()
@@ -235,59 +560,546 @@ impl CgroupManager {
             }
         }
 
-        if let Ok(content) = fs::read_to_string(self.cgroup_path.join("pids.current")) {
+        if let Ok(content) = fs::read_to_string(self.cgroup_path().join("pids.current")) {
             stats.pids_current = content.trim().parse().unwrap_or(0);
         }
 
-        if let Ok(content) = fs::read_to_string(self.cgroup_path.join("pids.max")) {
+        if let Ok(content) = fs::read_to_string(self.cgroup_path().join("pids.max")) {
             if content.trim() != "max" {
                 stats.pids_limit = content.trim().parse().ok();
             }
         }
 
+        if let Ok(content) = fs::read_to_string(self.cgroup_path().join("memory.stat")) {
+            for line in content.lines() {
+                let mut fields = line.split_whitespace();
+                match (fields.next(), fields.next().and_then(|v| v.parse::<u64>().ok())) {
+                    (Some("anon"), Some(v)) => stats.memory_anon = v,
+                    (Some("file"), Some(v)) => stats.memory_file = v,
+                    (Some("pgfault"), Some(v)) => stats.memory_pgfault = v,
+                    _ => {}
+                }
+            }
+        }
+
+        stats.io = parse_io_stat(&self.cgroup_path().join("io.stat"));
+        stats.hugetlb = parse_hugetlb_stats(&self.cgroup_path());
+
         Ok(stats)
     }
 
     pub fn cleanup(&self) -> Result<()> {
-        self.cleanup_internal()
+        self.detach_device_rules();
+
+        match self.driver {
+            CgroupDriver::Fs => self.cleanup_internal(),
+            CgroupDriver::Systemd => self.cleanup_systemd(),
+        }
+    }
+
+    /// Detaches the BPF device program if [`attach_device_rules`] loaded one.
+    /// Best-effort: the cgroup directory is about to be removed either way,
+    /// which also drops the kernel's last reference to an attached program.
+    fn detach_device_rules(&self) {
+        if let Some(fd) = self.device_prog_fd.borrow_mut().take() {
+            if let Err(err) = crate::devices::detach(&self.cgroup_path(), fd) {
+                println!("[Cgroups] Warning: failed to detach device program: {}", err);
+            }
+        }
+    }
+
+    /// Tears down the transient scope. Unlike the `Fs` driver, we don't
+    /// remove the cgroup directory ourselves — systemd owns that and removes
+    /// it once the unit is stopped and its last process has exited.
+    fn cleanup_systemd(&self) -> Result<()> {
+        println!("[Cgroups] Stopping systemd scope for container: {}", self.container_id);
+
+        let conn = Connection::new_session()
+            .context("Failed to connect to the systemd session D-Bus")?;
+        let proxy = conn.with_proxy(
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            Duration::from_secs(5),
+        );
+
+        let (_job,): (dbus::Path,) = proxy
+            .method_call(
+                "org.freedesktop.systemd1.Manager",
+                "StopUnit",
+                (self.scope_name(), "fail".to_string()),
+            )
+            .context("StopUnit failed")?;
+
+        println!("[Cgroups] Stopped transient unit {}", self.scope_name());
+        Ok(())
+    }
+
+    /// Walks the cgroup subtree depth-first, collecting every PID listed in
+    /// any `cgroup.procs` file — the container's own plus any nested
+    /// sub-cgroups it created (e.g. via a bundled process manager).
+    pub fn get_all_pids(&self) -> Result<Vec<Pid>> {
+        let mut pids = Vec::new();
+        collect_pids(&self.cgroup_path(), &mut pids)?;
+        Ok(pids)
     }
 
     fn cleanup_internal(&self) -> Result<()> {
-        if !self.cgroup_path.exists() {
+        let root = self.cgroup_path();
+        if !root.exists() {
             return Ok(());
         }
 
         println!("[Cgroups] Cleaning up cgroup: {}", self.container_id);
 
-        if let Ok(procs_content) = fs::read_to_string(self.cgroup_path.join("cgroup.procs")) {
-            let parent_procs = self.base_path.join("cgroup.procs");
-            for pid_line in procs_content.lines() {
-                if !pid_line.trim().is_empty() {
-                    let _ = fs::write(&parent_procs, pid_line);
-                    println!("[Cgroups] Moved PID {} back to parent cgroup", pid_line);
+        let parent_procs = self.base_path.join("cgroup.procs");
+        for pid in self.get_all_pids()? {
+            let _ = fs::write(&parent_procs, pid.to_string());
+            println!("[Cgroups] Moved PID {} back to parent cgroup", pid);
+        }
+
+        remove_child_dirs_bottom_up(&root)?;
+
+        delete_with_retry(&root, 10, Duration::from_secs(2))
+            .with_context(|| format!("Failed to remove cgroup directory: {}", root.display()))?;
+
+        println!("[Cgroups] Successfully cleaned up cgroup");
+        Ok(())
+    }
+
+    /// Freezes or thaws every task in the container's cgroup using the v2
+    /// freezer (`cgroup.freeze`), polling `cgroup.events` until the kernel
+    /// reports the transition as complete (it's asynchronous).
+    pub fn freeze(&self, state: FreezerState) -> Result<()> {
+        println!(
+            "[Cgroups] Requested {:?} for container: {}",
+            state, self.container_id
+        );
+        freeze_at_path(&self.cgroup_path(), state)
+    }
+}
+
+impl CgroupBackend for CgroupManager {
+    fn setup(&self, config: &CgroupsConfig, pid: Pid) -> Result<()> {
+        self.setup(config, pid)
+    }
+
+    fn apply_limits(&self, config: &CgroupsConfig) -> Result<()> {
+        self.apply_limits(config)
+    }
+
+    fn add_process(&self, pid: Pid) -> Result<()> {
+        self.add_process(pid)
+    }
+
+    fn get_stats(&self) -> Result<CgroupStats> {
+        self.get_stats()
+    }
+
+    fn cleanup(&self) -> Result<()> {
+        self.cleanup()
+    }
+
+    fn freeze(&self, state: FreezerState) -> Result<()> {
+        self.freeze(state)
+    }
+
+    fn cgroup_path(&self) -> PathBuf {
+        self.cgroup_path()
+    }
+}
+
+/// Legacy (v1) cgroup backend. Rather than assuming a delegated unified
+/// hierarchy, it locates each controller's own mount point by cross-
+/// referencing `/proc/self/cgroup` (which per-controller subtree we're
+/// already in) against `/proc/self/mountinfo` (where that hierarchy is
+/// mounted), then talks to the v1-named files under it.
+pub struct CgroupManagerV1 {
+    container_id: String,
+    memory_dir: Option<PathBuf>,
+    cpu_dir: Option<PathBuf>,
+    pids_dir: Option<PathBuf>,
+    freezer_dir: Option<PathBuf>,
+}
+
+impl CgroupManagerV1 {
+    pub fn new(container_id: String) -> Result<Self> {
+        let mounts = locate_v1_controller_mounts()?;
+        Ok(Self {
+            memory_dir: mounts.get("memory").map(|base| base.join(&container_id)),
+            cpu_dir: mounts.get("cpu").map(|base| base.join(&container_id)),
+            pids_dir: mounts.get("pids").map(|base| base.join(&container_id)),
+            freezer_dir: mounts.get("freezer").map(|base| base.join(&container_id)),
+            container_id,
+        })
+    }
+
+    fn controller_dirs(&self) -> impl Iterator<Item = &PathBuf> {
+        [&self.memory_dir, &self.cpu_dir, &self.pids_dir, &self.freezer_dir]
+            .into_iter()
+            .flatten()
+    }
+}
+
+impl CgroupBackend for CgroupManagerV1 {
+    fn setup(&self, config: &CgroupsConfig, pid: Pid) -> Result<()> {
+        println!(
+            "[Cgroups] Setting up v1 cgroups for container: {}",
+            self.container_id
+        );
+
+        for dir in self.controller_dirs() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create v1 cgroup dir: {}", dir.display()))?;
+        }
+
+        self.apply_limits(config)?;
+        self.add_process(pid)?;
+
+        println!("[Cgroups] Successfully configured v1 cgroups for PID: {}", pid);
+        Ok(())
+    }
+
+    fn apply_limits(&self, config: &CgroupsConfig) -> Result<()> {
+        if let Some(dir) = &self.memory_dir {
+            if let Some(memory_max) = &config.memory_max {
+                let value = if memory_max.eq_ignore_ascii_case("max") {
+                    "-1".to_string()
+                } else {
+                    parse_size_to_bytes(memory_max)?.to_string()
+                };
+                fs::write(dir.join("memory.limit_in_bytes"), value)
+                    .context("Failed to write memory.limit_in_bytes")?;
+            }
+
+            if let Some(swap_max) = &config.memory_swap_max {
+                let value = if swap_max.eq_ignore_ascii_case("max") {
+                    "-1".to_string()
+                } else {
+                    parse_size_to_bytes(swap_max)?.to_string()
+                };
+                fs::write(dir.join("memory.memsw.limit_in_bytes"), value)
+                    .context("Failed to write memory.memsw.limit_in_bytes")?;
+            }
+        }
+
+        if let Some(dir) = &self.cpu_dir {
+            if let Some(cpu_max) = &config.cpu_max {
+                let (quota_us, period_us) = parse_cpu_max(cpu_max)?;
+                fs::write(dir.join("cpu.cfs_period_us"), period_us.to_string())
+                    .context("Failed to write cpu.cfs_period_us")?;
+                let quota = quota_us.map(|q| q as i64).unwrap_or(-1);
+                fs::write(dir.join("cpu.cfs_quota_us"), quota.to_string())
+                    .context("Failed to write cpu.cfs_quota_us")?;
+            }
+
+            if let Some(weight) = config.cpu_weight {
+                // Inverse of the v2 shares->weight formula, since v1 has no
+                // weight concept and only understands cpu.shares (2-262144).
+                let shares = 2 + ((weight as u64).saturating_sub(1) * 262142) / 9999;
+                fs::write(dir.join("cpu.shares"), shares.to_string())
+                    .context("Failed to write cpu.shares")?;
+            }
+        }
+
+        if let Some(dir) = &self.pids_dir {
+            if let Some(pids_max) = &config.pids_max {
+                fs::write(dir.join("pids.max"), pids_max)
+                    .context("Failed to write pids.max")?;
+            }
+        }
+
+        println!("[Cgroups] Applied v1 resource limits");
+        Ok(())
+    }
+
+    fn add_process(&self, pid: Pid) -> Result<()> {
+        for dir in self.controller_dirs() {
+            fs::write(dir.join("cgroup.procs"), pid.to_string())
+                .with_context(|| format!("Failed to add PID {} to {}", pid, dir.display()))?;
+        }
+
+        println!("[Cgroups] Added PID {} to v1 cgroups", pid);
+        Ok(())
+    }
+
+    fn get_stats(&self) -> Result<CgroupStats> {
+        let mut stats = CgroupStats::default();
+
+        if let Some(dir) = &self.memory_dir {
+            if let Ok(content) = fs::read_to_string(dir.join("memory.usage_in_bytes")) {
+                stats.memory_usage = content.trim().parse().unwrap_or(0);
+            }
+            if let Ok(content) = fs::read_to_string(dir.join("memory.limit_in_bytes")) {
+                // An unset v1 limit reads back as a huge sentinel rather than "max".
+                stats.memory_limit = content
+                    .trim()
+                    .parse::<u64>()
+                    .ok()
+                    .filter(|v| *v < u64::MAX / 2);
+            }
+        }
+
+        if let Some(dir) = &self.cpu_dir {
+            if let Ok(content) = fs::read_to_string(dir.join("cpuacct.usage")) {
+                // cpuacct.usage is nanoseconds; normalise to usec like v2's cpu.stat.
+                stats.cpu_usage_usec = content.trim().parse::<u64>().unwrap_or(0) / 1000;
+            }
+        }
+
+        if let Some(dir) = &self.pids_dir {
+            if let Ok(content) = fs::read_to_string(dir.join("pids.current")) {
+                stats.pids_current = content.trim().parse().unwrap_or(0);
+            }
+            if let Ok(content) = fs::read_to_string(dir.join("pids.max")) {
+                if content.trim() != "max" {
+                    stats.pids_limit = content.trim().parse().ok();
                 }
             }
         }
 
-        fs::remove_dir(&self.cgroup_path).with_context(|| {
-            format!(
-                "Failed to remove cgroup directory: {}",
-                self.cgroup_path.display()
-            )
+        Ok(stats)
+    }
+
+    fn cleanup(&self) -> Result<()> {
+        println!(
+            "[Cgroups] Cleaning up v1 cgroups for container: {}",
+            self.container_id
+        );
+
+        for dir in self.controller_dirs() {
+            if !dir.exists() {
+                continue;
+            }
+
+            if let Ok(content) = fs::read_to_string(dir.join("cgroup.procs")) {
+                if let Some(parent_procs) = dir.parent().map(|p| p.join("cgroup.procs")) {
+                    for pid_line in content.lines() {
+                        let _ = fs::write(&parent_procs, pid_line);
+                    }
+                }
+            }
+
+            delete_with_retry(dir, 10, Duration::from_secs(2))
+                .with_context(|| format!("Failed to remove v1 cgroup dir: {}", dir.display()))?;
+        }
+
+        println!("[Cgroups] Successfully cleaned up v1 cgroups");
+        Ok(())
+    }
+
+    /// v1 has no `cgroup.events` to poll for completion, so unlike the v2
+    /// path this writes `freezer.state` and returns as soon as the kernel
+    /// accepts it - the write itself is synchronous on v1.
+    fn freeze(&self, state: FreezerState) -> Result<()> {
+        let dir = self.freezer_dir.as_ref().ok_or_else(|| {
+            anyhow!("Freezing is not supported on cgroup v1 without the freezer controller mounted")
         })?;
 
-        println!("[Cgroups] Successfully cleaned up cgroup");
+        let value = match state {
+            FreezerState::Frozen => "FROZEN",
+            FreezerState::Thawed => "THAWED",
+        };
+        fs::write(dir.join("freezer.state"), value)
+            .with_context(|| format!("Failed to write {value} to {}", dir.join("freezer.state").display()))?;
+
+        println!(
+            "[Cgroups] Set v1 freezer.state to {value} for container: {}",
+            self.container_id
+        );
         Ok(())
     }
+
+    fn cgroup_path(&self) -> PathBuf {
+        self.memory_dir
+            .clone()
+            .or_else(|| self.cpu_dir.clone())
+            .or_else(|| self.pids_dir.clone())
+            .or_else(|| self.freezer_dir.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Parses `/proc/self/mountinfo` to find where each v1 controller hierarchy
+/// is mounted, then joins it with our own subtree from `/proc/self/cgroup`
+/// so writes land in the same relative path the kernel already placed us
+/// under (mirroring what `get_user_cgroup_base` does for the v2 case).
+fn locate_v1_controller_mounts() -> Result<std::collections::HashMap<String, PathBuf>> {
+    let cgroup_file =
+        fs::read_to_string("/proc/self/cgroup").context("Failed to read /proc/self/cgroup")?;
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo")
+        .context("Failed to read /proc/self/mountinfo")?;
+
+    let mut subtrees: std::collections::HashMap<String, String> = std::collections::HashMap::new();
+    for line in cgroup_file.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(_id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        for controller in controllers.split(',') {
+            if !controller.is_empty() {
+                subtrees.insert(controller.to_string(), path.to_string());
+            }
+        }
+    }
+
+    let mut mounts = std::collections::HashMap::new();
+    for line in mountinfo.lines() {
+        let Some((pre, post)) = line.split_once(" - ") else {
+            continue;
+        };
+
+        let mut post_fields = post.split_whitespace();
+        if post_fields.next() != Some("cgroup") {
+            continue;
+        }
+        let Some(super_options) = post_fields.nth(1) else {
+            continue;
+        };
+
+        let pre_fields: Vec<&str> = pre.split_whitespace().collect();
+        let Some(mount_point) = pre_fields.get(4) else {
+            continue;
+        };
+
+        for option in super_options.split(',') {
+            if let Some(relative) = subtrees.get(option) {
+                let base = if relative == "/" {
+                    PathBuf::from(mount_point)
+                } else {
+                    PathBuf::from(mount_point).join(relative.trim_start_matches('/'))
+                };
+                mounts.insert(option.to_string(), base);
+            }
+        }
+    }
+
+    if mounts.is_empty() {
+        return Err(anyhow!(
+            "No cgroup v1 controller mounts found in /proc/self/mountinfo"
+        ));
+    }
+
+    Ok(mounts)
+}
+
+/// State requested from the cgroup v2 freezer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FreezerState {
+    Frozen,
+    Thawed,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct CgroupStats {
     pub memory_usage: u64,
     pub memory_limit: Option<u64>,
+    pub memory_anon: u64,
+    pub memory_file: u64,
+    pub memory_pgfault: u64,
     pub cpu_usage_usec: u64,
     pub pids_current: u32,
     pub pids_limit: Option<u32>,
+    pub io: Vec<IoDeviceStats>,
+    pub hugetlb: Vec<HugetlbStats>,
+}
+
+/// Per-device IO accounting parsed from `io.stat`.
+#[derive(Debug, Default, Clone)]
+pub struct IoDeviceStats {
+    pub device: String,
+    pub rbytes: u64,
+    pub wbytes: u64,
+    pub rios: u64,
+    pub wios: u64,
+}
+
+/// Usage for one hugetlb page size, e.g. `"2MB"`.
+#[derive(Debug, Default, Clone)]
+pub struct HugetlbStats {
+    pub size: String,
+    pub current_bytes: u64,
+}
+
+/// Parses `io.stat` lines of the form `<major>:<minor> rbytes=.. wbytes=.. rios=.. wios=..`.
+fn parse_io_stat(path: &Path) -> Vec<IoDeviceStats> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?.to_string();
+            let mut stats = IoDeviceStats {
+                device,
+                ..Default::default()
+            };
+
+            for field in fields {
+                let (key, value) = field.split_once('=')?;
+                let value: u64 = value.parse().ok()?;
+                match key {
+                    "rbytes" => stats.rbytes = value,
+                    "wbytes" => stats.wbytes = value,
+                    "rios" => stats.rios = value,
+                    "wios" => stats.wios = value,
+                    _ => {}
+                }
+            }
+
+            Some(stats)
+        })
+        .collect()
+}
+
+/// Scans the cgroup directory for `hugetlb.<size>.current` files and derives
+/// a human-readable size moniker from the kB-denominated directory suffix
+/// (e.g. `hugetlb.2048kB.current` -> `"2MB"`).
+fn parse_hugetlb_stats(cgroup_path: &Path) -> Vec<HugetlbStats> {
+    let Ok(entries) = fs::read_dir(cgroup_path) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let size_token = name
+                .strip_prefix("hugetlb.")?
+                .strip_suffix(".current")?
+                .to_string();
+            let current_bytes: u64 = fs::read_to_string(entry.path())
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+
+            Some(HugetlbStats {
+                size: humanize_hugetlb_size(&size_token),
+                current_bytes,
+            })
+        })
+        .collect()
+}
+
+/// Converts a `hugetlb.<N>kB.current` size suffix into a human moniker:
+/// >= 2^20 kB renders as GB, >= 2^10 kB as MB, otherwise KB.
+fn humanize_hugetlb_size(size_token: &str) -> String {
+    let kb: u64 = size_token
+        .strip_suffix("kB")
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(0);
+
+    if kb >= 1 << 20 {
+        format!("{}GB", kb / (1 << 20))
+    } else if kb >= 1 << 10 {
+        format!("{}MB", kb / (1 << 10))
+    } else {
+        format!("{}KB", kb)
+    }
 }
 
 impl Drop for CgroupManager {
@@ -364,6 +1176,119 @@ pub fn get_user_cgroup_base() -> Result<PathBuf> {
     ))
 }
 
+/// Depth-bounded search for a directory named `name` under `root`, used to
+/// find the cgroup systemd picked for a transient scope without having to
+/// reimplement systemd's slice-naming rules.
+fn find_dir_named(root: &Path, name: &str, max_depth: u32) -> Option<PathBuf> {
+    if max_depth == 0 || !root.is_dir() {
+        return None;
+    }
+
+    let entries = fs::read_dir(root).ok()?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        if path.file_name().and_then(|n| n.to_str()) == Some(name) {
+            return Some(path);
+        }
+        if let Some(found) = find_dir_named(&path, name, max_depth - 1) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Recursively reads `cgroup.procs` files under `dir`, depth-first, so PIDs
+/// parked in nested sub-cgroups aren't left behind when a container leaks
+/// them there (e.g. a container-managed process supervisor).
+fn collect_pids(dir: &Path, out: &mut Vec<Pid>) -> Result<()> {
+    if let Ok(content) = fs::read_to_string(dir.join("cgroup.procs")) {
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Ok(raw) = line.parse::<i32>() {
+                out.push(Pid::from_raw(raw));
+            }
+        }
+    }
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                collect_pids(&path, out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Removes every empty child directory under `root`, deepest first, so that
+/// nested sub-cgroups don't block removal of `root` itself once it's empty.
+fn remove_child_dirs_bottom_up(root: &Path) -> Result<()> {
+    let mut children = Vec::new();
+    if let Ok(entries) = fs::read_dir(root) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                children.push(path);
+            }
+        }
+    }
+
+    for child in children {
+        remove_child_dirs_bottom_up(&child)?;
+        let _ = delete_with_retry(&child, 5, Duration::from_secs(1));
+    }
+
+    Ok(())
+}
+
+/// Attempts `fs::remove_dir`, retrying on `EBUSY` with exponential backoff
+/// (starting at 10ms, doubling each attempt) up to `max_attempts` times or
+/// until `max_duration` has elapsed, whichever comes first.
+fn delete_with_retry(path: &Path, max_attempts: u32, max_duration: Duration) -> Result<()> {
+    let start = std::time::Instant::now();
+    let mut delay = Duration::from_millis(10);
+
+    for attempt in 0..max_attempts {
+        match fs::remove_dir(path) {
+            Ok(()) => return Ok(()),
+            Err(e) if e.raw_os_error() == Some(nix::libc::EBUSY) => {
+                if start.elapsed() >= max_duration {
+                    return Err(anyhow!(
+                        "Timed out removing {} after {} attempt(s): still busy",
+                        path.display(),
+                        attempt + 1
+                    ));
+                }
+                println!(
+                    "[Cgroups] {} busy, retrying in {:?} (attempt {}/{})",
+                    path.display(),
+                    delay,
+                    attempt + 1,
+                    max_attempts
+                );
+                std::thread::sleep(delay);
+                delay *= 2;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e).with_context(|| format!("Failed to remove {}", path.display())),
+        }
+    }
+
+    Err(anyhow!(
+        "Exhausted {} retries removing {}",
+        max_attempts,
+        path.display()
+    ))
+}
+
 fn can_write_to_cgroup(path: &PathBuf) -> bool {
     if !path.exists() {
         return false;
@@ -412,16 +1337,78 @@ pub fn setup_cgroups(
     pid: Pid,
     _base: &Path,
 ) -> Result<PathBuf> {
-    let manager = CgroupManager::new(container_id.to_string())?;
-    manager.setup(config, pid)?;
-    Ok(manager.cgroup_path.clone())
+    let backend: Box<dyn CgroupBackend> = match detect_cgroup_setup() {
+        CgroupVersion::V2 => {
+            Box::new(CgroupManager::with_driver(container_id.to_string(), config.driver)?)
+        }
+        CgroupVersion::V1 => Box::new(CgroupManagerV1::new(container_id.to_string())?),
+    };
+
+    backend.setup(config, pid)?;
+    Ok(backend.cgroup_path())
 }
 
-pub fn cleanup_cgroups(cgroup_path: &Path) -> Result<()> {
+pub fn cleanup_cgroups(cgroup_path: &Path, driver: CgroupDriver) -> Result<()> {
     if let Some(container_id) = cgroup_path.file_name().and_then(|n| n.to_str()) {
-        let manager = CgroupManager::new(container_id.to_string())?;
-        manager.cleanup()
+        let backend: Box<dyn CgroupBackend> = match detect_cgroup_setup() {
+            CgroupVersion::V2 => Box::new(CgroupManager::with_driver(container_id.to_string(), driver)?),
+            CgroupVersion::V1 => Box::new(CgroupManagerV1::new(container_id.to_string())?),
+        };
+        backend.cleanup()
     } else {
         fs::remove_dir(cgroup_path).context("Failed to remove cgroup dir")
     }
 }
+
+/// Freezes every task under `cgroup_path` so the CLI layer can implement
+/// `bento pause` without needing a full `CgroupManager` (the path is already
+/// known from the saved container state).
+pub fn freeze_container(cgroup_path: &Path) -> Result<()> {
+    freeze_at_path(cgroup_path, FreezerState::Frozen)
+}
+
+/// Thaws a container frozen by [`freeze_container`], backing `bento resume`.
+pub fn thaw_container(cgroup_path: &Path) -> Result<()> {
+    freeze_at_path(cgroup_path, FreezerState::Thawed)
+}
+
+fn freeze_at_path(cgroup_path: &Path, state: FreezerState) -> Result<()> {
+    let freeze_file = cgroup_path.join("cgroup.freeze");
+    if !freeze_file.exists() {
+        return Err(anyhow!(
+            "cgroup.freeze not available at {} — freezer controller not delegated",
+            freeze_file.display()
+        ));
+    }
+
+    let value = match state {
+        FreezerState::Frozen => "1",
+        FreezerState::Thawed => "0",
+    };
+    fs::write(&freeze_file, value)
+        .with_context(|| format!("Failed to write {} to {}", value, freeze_file.display()))?;
+
+    let events_file = cgroup_path.join("cgroup.events");
+    let expected = match state {
+        FreezerState::Frozen => "frozen 1",
+        FreezerState::Thawed => "frozen 0",
+    };
+
+    let mut delay = Duration::from_millis(10);
+    for attempt in 0..10 {
+        if let Ok(content) = fs::read_to_string(&events_file) {
+            if content.lines().any(|line| line.trim() == expected) {
+                println!("[Cgroups] Reached {:?} after {} attempt(s)", state, attempt + 1);
+                return Ok(());
+            }
+        }
+        std::thread::sleep(delay);
+        delay *= 2;
+    }
+
+    Err(anyhow!(
+        "Timed out waiting for cgroup to reach {:?} (checked {})",
+        state,
+        events_file.display()
+    ))
+}
@@ -20,7 +20,7 @@ pub enum ConfigError {
     Invalid(String),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum NamespaceType {
     Pid,
@@ -76,6 +76,12 @@ pub struct Root {
     pub path: PathBuf,
     #[serde(default)]
     pub readonly: bool,
+    /// Extra lowerdirs to stack under `path` via overlayfs, highest-priority
+    /// first. Empty (the default) keeps the single-rootfs self-bind-mount
+    /// behavior; non-empty makes `path` a merged overlay view instead.
+    #[serde(default)]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    pub layers: Vec<PathBuf>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -111,6 +117,8 @@ pub struct Linux {
     pub namespaces: Vec<Namespace>,
     #[serde(default)]
     pub resources: Option<Resources>,
+    #[serde(default)]
+    pub seccomp: Option<crate::config2::SeccompConfig>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -134,18 +142,52 @@ pub struct Resources {
     pub memory: Option<Memory>,
     #[serde(default)]
     pub cpu: Option<Cpu>,
+    #[serde(default)]
+    pub pids: Option<Pids>,
+    #[serde(default)]
+    pub devices: Vec<DeviceRule>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Memory {
     #[serde(default)]
     pub limit: Option<i64>,
+    #[serde(default)]
+    pub reservation: Option<i64>,
+    #[serde(default)]
+    pub swap: Option<i64>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Cpu {
     #[serde(default)]
     pub shares: Option<u64>,
+    #[serde(default)]
+    pub quota: Option<i64>,
+    #[serde(default)]
+    pub period: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Pids {
+    pub limit: i64,
+}
+
+/// One entry of `linux.resources.devices`, matching the OCI runtime-spec
+/// device whitelist rule shape.
+#[derive(Debug, Deserialize, Clone)]
+pub struct DeviceRule {
+    pub allow: bool,
+    /// `"a"` (all), `"c"` (char) or `"b"` (block).
+    #[serde(rename = "type")]
+    pub rule_type: String,
+    #[serde(default)]
+    pub major: Option<i64>,
+    #[serde(default)]
+    pub minor: Option<i64>,
+    /// Subset of `"rwm"`.
+    #[serde(default)]
+    pub access: String,
 }
 
 // THis will impl serde's deserialize on config
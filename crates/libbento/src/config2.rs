@@ -1,9 +1,8 @@
-// This file is trying to mimic the config.json file and call the seccomp module.
-use anyhow::{Context, Result};
+// Types mirroring the OCI runtime spec's `linux.seccomp` section, parsed by
+// `config::Linux::seccomp` and consumed by `crate::seccomp::SeccompFilter`.
 use serde::Deserialize;
-use std::{fs::File, io::Read, path::PathBuf};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct SeccompConfig {
     #[serde(rename = "defaultAction")]
     pub default_action: String, // this is for unspecified syscalls
@@ -11,23 +10,23 @@ pub struct SeccompConfig {
     pub syscalls: Vec<SyscallRule>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct SyscallRule {
     pub names: Vec<String>,
     pub action: String, // like Allow and Kill actions
+    #[serde(default)]
+    pub args: Vec<ArgCompare>,
 }
 
-fn get_path(container_id: &str) -> PathBuf {
-    PathBuf::from(format!("/run/container/{container_id}/config.json"))
-}
-
-pub fn load_config(container_id: &str) -> Result<SeccompConfig> {
-    let config_path = get_path(container_id);
-    let mut file = File::open(&config_path)
-        .with_context(|| format!("Failed to open config file at {}", config_path.display()))?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)
-        .with_context(|| format!("Failed to read config file at {}", config_path.display()))?;
-    serde_json::from_str(&contents)
-        .with_context(|| format!("Failed to parse config file at {}", config_path.display()))
+/// One argument constraint from the OCI seccomp spec's `syscalls[].args`,
+/// e.g. `{"index": 3, "value": 32, "op": "SCMP_CMP_MASKED_EQ"}` to match
+/// `mount` calls whose flags (fourth argument) have `MS_REMOUNT` (32) set. Translated into a
+/// `libseccomp::ScmpArgCompare` before being handed to the kernel.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArgCompare {
+    pub index: u32,
+    pub value: u64,
+    #[serde(default)]
+    pub value_two: Option<u64>,
+    pub op: String, // e.g. SCMP_CMP_EQ, SCMP_CMP_MASKED_EQ, SCMP_CMP_GE
 }
@@ -0,0 +1,445 @@
+// crates/libbento/src/devices.rs
+//
+// Enforces the OCI `linux.resources.devices` whitelist on cgroup v2 hosts.
+// v2 dropped the `devices.allow`/`devices.deny` files v1 had, so the kernel
+// expects a small BPF_PROG_TYPE_CGROUP_DEVICE program attached to the
+// container's cgroup instead; this module compiles our rule list into one
+// and attaches/detaches it alongside `CgroupManager::setup`/`cleanup`.
+
+use anyhow::{Context, Result, anyhow};
+use std::os::unix::io::RawFd;
+use std::path::Path;
+
+use crate::config::DeviceRule;
+
+/// `BPF_DEVCG_DEV_*` from `linux/bpf.h`, packed into the upper 16 bits of
+/// `bpf_cgroup_dev_ctx.access_type`.
+const BPF_DEVCG_DEV_BLOCK: u32 = 1;
+const BPF_DEVCG_DEV_CHAR: u32 = 2;
+
+/// `BPF_DEVCG_ACC_*` from `linux/bpf.h`, the lower 16 bits of `access_type`.
+const BPF_DEVCG_ACC_READ: u32 = 1;
+const BPF_DEVCG_ACC_WRITE: u32 = 2;
+const BPF_DEVCG_ACC_MKNOD: u32 = 4;
+
+const BPF_PROG_TYPE_CGROUP_DEVICE: u32 = 15;
+const BPF_CGROUP_DEVICE: u32 = 5;
+const BPF_PROG_LOAD: u64 = 5;
+const BPF_PROG_ATTACH: u64 = 8;
+const BPF_PROG_DETACH: u64 = 9;
+
+/// The OCI default device whitelist: the handful of pseudo-devices every
+/// container needs (null/zero/full/random/urandom, the controlling tty,
+/// ptmx/devpts) mirroring what `runc` and the reference spec allow by
+/// default when no explicit rules are given.
+pub fn default_device_rules() -> Vec<DeviceRule> {
+    let rule = |major: i64, minor: i64| DeviceRule {
+        allow: true,
+        rule_type: "c".to_string(),
+        major: Some(major),
+        minor: Some(minor),
+        access: "rwm".to_string(),
+    };
+
+    vec![
+        rule(1, 3), // /dev/null
+        rule(1, 5), // /dev/zero
+        rule(1, 7), // /dev/full
+        rule(1, 8), // /dev/random
+        rule(1, 9), // /dev/urandom
+        rule(5, 0), // /dev/tty
+        rule(5, 1), // /dev/console
+        rule(5, 2), // /dev/ptmx
+        DeviceRule {
+            allow: true,
+            rule_type: "c".to_string(),
+            major: Some(136),
+            minor: None, // wildcard: all devpts ptys
+            access: "rwm".to_string(),
+        },
+    ]
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct BpfInsn {
+    code: u8,
+    regs: u8,
+    off: i16,
+    imm: i32,
+}
+
+impl BpfInsn {
+    fn new(code: u8, dst: u8, src: u8, off: i16, imm: i32) -> Self {
+        Self {
+            code,
+            regs: (src << 4) | (dst & 0x0f),
+            off,
+            imm,
+        }
+    }
+}
+
+const BPF_LDX_W: u8 = 0x61; // BPF_LDX | BPF_W | BPF_MEM
+const BPF_ALU64_MOV_K: u8 = 0xb7; // BPF_ALU64 | BPF_MOV | BPF_K
+const BPF_ALU64_AND_K: u8 = 0x57; // BPF_ALU64 | BPF_AND | BPF_K
+const BPF_JMP_JEQ_K: u8 = 0x15; // BPF_JMP | BPF_JEQ | BPF_K
+const BPF_JMP_JNE_K: u8 = 0x55; // BPF_JMP | BPF_JNE | BPF_K
+const BPF_JMP_EXIT: u8 = 0x95; // BPF_JMP | BPF_EXIT
+
+const REG_ARG_CTX: u8 = 1; // r1: context pointer on entry
+const REG_RET: u8 = 0; // r0: return value
+const REG_SCRATCH: u8 = 2; // r2: scratch for loaded ctx fields
+
+/// Layout of `struct bpf_cgroup_dev_ctx` (`linux/bpf.h`): three consecutive
+/// `u32` fields starting at the context pointer.
+const CTX_OFF_ACCESS_TYPE: i16 = 0;
+const CTX_OFF_MAJOR: i16 = 4;
+const CTX_OFF_MINOR: i16 = 8;
+
+fn device_type_flag(rule_type: &str) -> u32 {
+    match rule_type {
+        "b" => BPF_DEVCG_DEV_BLOCK,
+        _ => BPF_DEVCG_DEV_CHAR, // "c"; the "a" (all) wildcard never gets here - compile_program skips the type check for it
+    }
+}
+
+fn access_mask(access: &str) -> u32 {
+    let mut mask = 0;
+    if access.contains('r') {
+        mask |= BPF_DEVCG_ACC_READ;
+    }
+    if access.contains('w') {
+        mask |= BPF_DEVCG_ACC_WRITE;
+    }
+    if access.contains('m') {
+        mask |= BPF_DEVCG_ACC_MKNOD;
+    }
+    mask
+}
+
+/// Compiles `rules` into a `BPF_PROG_TYPE_CGROUP_DEVICE` program: each rule
+/// becomes a handful of compares against the context's access/major/minor
+/// fields. A mismatch jumps past the rest of that rule to the next one's
+/// first instruction; a full match sets `r0` to the rule's verdict and
+/// returns immediately. Falling through every rule denies by default.
+///
+/// OCI device rules are last-match-wins, so `rules` is walked in reverse -
+/// the last entry in `rules` becomes the *first* thing the program checks,
+/// making it take priority the way a later, more specific rule should over
+/// an earlier, broader one (e.g. a bundle's own allow rule over a
+/// deny-all default listed before it in `rules`).
+fn compile_program(rules: &[DeviceRule]) -> Vec<BpfInsn> {
+    let mut insns = Vec::new();
+
+    for rule in rules.iter().rev() {
+        let mut checks = Vec::new();
+
+        if rule.rule_type != "a" {
+            checks.push((
+                CTX_OFF_ACCESS_TYPE,
+                0xffff_0000u32,
+                device_type_flag(&rule.rule_type) << 16,
+            ));
+        }
+        if let Some(major) = rule.major {
+            checks.push((CTX_OFF_MAJOR, u32::MAX, major as u32));
+        }
+        if let Some(minor) = rule.minor {
+            checks.push((CTX_OFF_MINOR, u32::MAX, minor as u32));
+        }
+
+        let access = access_mask(&rule.access);
+        let mut rule_insns: Vec<BpfInsn> = Vec::new();
+        let mut mismatch_jumps: Vec<usize> = Vec::new();
+
+        // r2 = ctx-><field>; if (r2 & mask) != expected, jump to the next rule.
+        for (off, mask, expected) in &checks {
+            rule_insns.push(BpfInsn::new(BPF_LDX_W, REG_SCRATCH, REG_ARG_CTX, *off, 0));
+            if *mask != u32::MAX {
+                rule_insns.push(BpfInsn::new(BPF_ALU64_AND_K, REG_SCRATCH, 0, 0, *mask as i32));
+            }
+            mismatch_jumps.push(rule_insns.len());
+            rule_insns.push(BpfInsn::new(BPF_JMP_JNE_K, REG_SCRATCH, 0, 0, *expected as i32));
+        }
+
+        // access_type's low 16 bits are the requested rwm bitmask: require
+        // the rule's allowed access to be a superset of what's requested, by
+        // checking that no bit outside the rule's mask is set in the request.
+        rule_insns.push(BpfInsn::new(BPF_LDX_W, REG_SCRATCH, REG_ARG_CTX, CTX_OFF_ACCESS_TYPE, 0));
+        rule_insns.push(BpfInsn::new(BPF_ALU64_AND_K, REG_SCRATCH, 0, 0, 0xffff));
+        rule_insns.push(BpfInsn::new(BPF_ALU64_AND_K, REG_SCRATCH, 0, 0, !(access as i32)));
+        mismatch_jumps.push(rule_insns.len());
+        rule_insns.push(BpfInsn::new(BPF_JMP_JNE_K, REG_SCRATCH, 0, 0, 0));
+
+        rule_insns.push(BpfInsn::new(BPF_ALU64_MOV_K, REG_RET, 0, 0, rule.allow as i32));
+        rule_insns.push(BpfInsn::new(BPF_JMP_EXIT, 0, 0, 0, 0));
+
+        // Every "jne" above jumps to whatever comes right after this rule
+        // (the next rule, or the default-deny tail for the last one).
+        let total = rule_insns.len();
+        for pos in mismatch_jumps {
+            rule_insns[pos].off = (total - pos - 1) as i16;
+        }
+
+        insns.extend(rule_insns);
+    }
+
+    // Default-deny if no rule matched.
+    insns.push(BpfInsn::new(BPF_ALU64_MOV_K, REG_RET, 0, 0, 0));
+    insns.push(BpfInsn::new(BPF_JMP_EXIT, 0, 0, 0, 0));
+
+    insns
+}
+
+/// Compiles `rules` into a program, loads it, attaches it to the cgroup at
+/// `cgroup_path`, and returns the
+/// program fd so it can be passed to [`detach`] on cleanup.
+pub fn attach(cgroup_path: &Path, rules: &[DeviceRule]) -> Result<RawFd> {
+    let program = compile_program(rules);
+    let license = std::ffi::CString::new("GPL").unwrap();
+
+    let prog_fd = unsafe {
+        bpf_prog_load(BPF_PROG_TYPE_CGROUP_DEVICE, &program, &license)
+    }
+    .context("Failed to load BPF_PROG_TYPE_CGROUP_DEVICE program")?;
+
+    let cgroup_fd = nix::fcntl::open(
+        cgroup_path,
+        nix::fcntl::OFlag::O_RDONLY | nix::fcntl::OFlag::O_DIRECTORY,
+        nix::sys::stat::Mode::empty(),
+    )
+    .with_context(|| format!("Failed to open cgroup dir: {}", cgroup_path.display()))?;
+
+    let attach_result = unsafe { bpf_prog_attach(prog_fd, cgroup_fd, BPF_CGROUP_DEVICE) };
+    let _ = nix::unistd::close(cgroup_fd);
+    attach_result.context("BPF_PROG_ATTACH (BPF_CGROUP_DEVICE) failed")?;
+
+    println!(
+        "[Devices] Attached {} device rule(s) to {}",
+        rules.len(),
+        cgroup_path.display()
+    );
+    Ok(prog_fd)
+}
+
+/// Detaches and closes the program fd returned by [`attach`].
+pub fn detach(cgroup_path: &Path, prog_fd: RawFd) -> Result<()> {
+    let cgroup_fd = nix::fcntl::open(
+        cgroup_path,
+        nix::fcntl::OFlag::O_RDONLY | nix::fcntl::OFlag::O_DIRECTORY,
+        nix::sys::stat::Mode::empty(),
+    )
+    .with_context(|| format!("Failed to open cgroup dir: {}", cgroup_path.display()))?;
+
+    let result = unsafe { bpf_prog_detach(cgroup_fd, BPF_CGROUP_DEVICE) };
+    let _ = nix::unistd::close(cgroup_fd);
+    let _ = nix::unistd::close(prog_fd);
+    result.context("BPF_PROG_DETACH (BPF_CGROUP_DEVICE) failed")
+}
+
+unsafe fn bpf_prog_load(prog_type: u32, insns: &[BpfInsn], license: &std::ffi::CString) -> Result<RawFd> {
+    #[repr(C)]
+    struct BpfAttr {
+        prog_type: u32,
+        insn_cnt: u32,
+        insns: u64,
+        license: u64,
+        log_level: u32,
+        log_size: u32,
+        log_buf: u64,
+        kern_version: u32,
+    }
+
+    let attr = BpfAttr {
+        prog_type,
+        insn_cnt: insns.len() as u32,
+        insns: insns.as_ptr() as u64,
+        license: license.as_ptr() as u64,
+        log_level: 0,
+        log_size: 0,
+        log_buf: 0,
+        kern_version: 0,
+    };
+
+    let ret = nix::libc::syscall(
+        nix::libc::SYS_bpf as nix::libc::c_long,
+        BPF_PROG_LOAD,
+        &attr as *const BpfAttr,
+        std::mem::size_of::<BpfAttr>(),
+    );
+
+    if ret < 0 {
+        return Err(anyhow!(
+            "bpf(BPF_PROG_LOAD) failed: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(ret as RawFd)
+}
+
+unsafe fn bpf_prog_attach(prog_fd: RawFd, target_fd: RawFd, attach_type: u32) -> Result<()> {
+    #[repr(C)]
+    struct BpfAttr {
+        target_fd: u32,
+        attach_bpf_fd: u32,
+        attach_type: u32,
+        attach_flags: u32,
+    }
+
+    let attr = BpfAttr {
+        target_fd: target_fd as u32,
+        attach_bpf_fd: prog_fd as u32,
+        attach_type,
+        attach_flags: 0,
+    };
+
+    let ret = nix::libc::syscall(
+        nix::libc::SYS_bpf as nix::libc::c_long,
+        BPF_PROG_ATTACH,
+        &attr as *const BpfAttr,
+        std::mem::size_of::<BpfAttr>(),
+    );
+
+    if ret < 0 {
+        return Err(anyhow!(
+            "bpf(BPF_PROG_ATTACH) failed: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(())
+}
+
+unsafe fn bpf_prog_detach(target_fd: RawFd, attach_type: u32) -> Result<()> {
+    #[repr(C)]
+    struct BpfAttr {
+        target_fd: u32,
+        attach_bpf_fd: u32,
+        attach_type: u32,
+        attach_flags: u32,
+    }
+
+    let attr = BpfAttr {
+        target_fd: target_fd as u32,
+        attach_bpf_fd: 0,
+        attach_type,
+        attach_flags: 0,
+    };
+
+    let ret = nix::libc::syscall(
+        nix::libc::SYS_bpf as nix::libc::c_long,
+        BPF_PROG_DETACH,
+        &attr as *const BpfAttr,
+        std::mem::size_of::<BpfAttr>(),
+    );
+
+    if ret < 0 {
+        return Err(anyhow!(
+            "bpf(BPF_PROG_DETACH) failed: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_device_type_flag() {
+        assert_eq!(device_type_flag("b"), BPF_DEVCG_DEV_BLOCK);
+        assert_eq!(device_type_flag("c"), BPF_DEVCG_DEV_CHAR);
+        assert_eq!(device_type_flag("a"), BPF_DEVCG_DEV_CHAR); // "a" never reaches the type check
+    }
+
+    #[test]
+    fn test_access_mask() {
+        assert_eq!(access_mask(""), 0);
+        assert_eq!(access_mask("r"), BPF_DEVCG_ACC_READ);
+        assert_eq!(access_mask("w"), BPF_DEVCG_ACC_WRITE);
+        assert_eq!(access_mask("m"), BPF_DEVCG_ACC_MKNOD);
+        assert_eq!(
+            access_mask("rwm"),
+            BPF_DEVCG_ACC_READ | BPF_DEVCG_ACC_WRITE | BPF_DEVCG_ACC_MKNOD
+        );
+    }
+
+    fn rule(rule_type: &str, major: Option<i64>, minor: Option<i64>, access: &str, allow: bool) -> DeviceRule {
+        DeviceRule {
+            allow,
+            rule_type: rule_type.to_string(),
+            major,
+            minor,
+            access: access.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_compile_program_ends_in_default_deny() {
+        let insns = compile_program(&[]);
+        // An empty rule list should compile straight to the default-deny tail.
+        assert_eq!(insns.len(), 2);
+        assert_eq!(insns[0].code, BPF_ALU64_MOV_K);
+        assert_eq!(insns[0].imm, 0);
+        assert_eq!(insns[1].code, BPF_JMP_EXIT);
+    }
+
+    #[test]
+    fn test_compile_program_single_rule_returns_allow() {
+        let insns = compile_program(&[rule("c", Some(1), Some(3), "rwm", true)]);
+        let last_two = &insns[insns.len() - 2..];
+        assert_eq!(last_two[0].code, BPF_ALU64_MOV_K);
+        assert_eq!(last_two[0].imm, 0); // default-deny tail is always appended
+        assert_eq!(last_two[1].code, BPF_JMP_EXIT);
+
+        // The rule itself should set r0 to its own `allow` verdict and exit
+        // before falling through to the default-deny tail.
+        let exit_positions: Vec<usize> = insns
+            .iter()
+            .enumerate()
+            .filter(|(_, insn)| insn.code == BPF_JMP_EXIT)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(exit_positions.len(), 2);
+        let rule_verdict = insns[exit_positions[0] - 1];
+        assert_eq!(rule_verdict.code, BPF_ALU64_MOV_K);
+        assert_eq!(rule_verdict.imm, 1);
+    }
+
+    #[test]
+    fn test_compile_program_wildcard_rule_skips_type_check() {
+        let with_type = compile_program(&[rule("c", None, None, "rwm", true)]);
+        let wildcard = compile_program(&[rule("a", None, None, "rwm", true)]);
+        // The "a" rule type skips the access_type-field compare that "c" emits.
+        assert!(wildcard.len() < with_type.len());
+    }
+
+    #[test]
+    fn test_compile_program_mismatch_jumps_land_past_the_rule() {
+        let insns = compile_program(&[
+            rule("c", Some(1), Some(3), "rwm", true),
+            rule("c", Some(1), Some(5), "rwm", true),
+        ]);
+        // Every JNE's jump target (its index + its offset + 1) should land on
+        // a later instruction, never past the end of the program.
+        for (i, insn) in insns.iter().enumerate() {
+            if insn.code == BPF_JMP_JNE_K {
+                let target = i as i16 + insn.off + 1;
+                assert!(
+                    (target as usize) <= insns.len(),
+                    "jump at {i} targets {target}, out of bounds"
+                );
+                assert!(target as usize > i, "jump at {i} doesn't move forward");
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_device_rules_shape() {
+        let rules = default_device_rules();
+        assert!(rules.iter().all(|r| r.allow && r.access == "rwm"));
+        assert!(rules.iter().any(|r| r.major == Some(1) && r.minor == Some(3))); // /dev/null
+        assert!(rules.iter().any(|r| r.major == Some(136) && r.minor.is_none())); // devpts wildcard
+    }
+}
@@ -11,12 +11,8 @@ use std::{
 };
 use serde_json;
 
-fn get_rootfs(container_id : &str) -> Result<(PathBuf, PathBuf)> {
+fn get_rootfs(container_id: &str, config: &serde_json::Value) -> Result<(PathBuf, PathBuf)> {
 
-    let config_path = PathBuf::from(format!("/run/container/{container_id}/config.json"));
-    let config_content = fs::read_to_string(&config_path)?;
-    let config : serde_json::Value = serde_json::from_str(&config_content)?;
- 
     let rootfs_path = match config["root"]["path"].as_str() {
         Some(path) => path,
         None => return Err(anyhow::anyhow!("Missing or invalid root.path in config.json in {container_id}."))
@@ -36,35 +32,439 @@ fn get_rootfs(container_id : &str) -> Result<(PathBuf, PathBuf)> {
     Ok((rootfs, old_root))
 }
 
-pub fn prepare_rootfs(container_id: &str) -> Result<PathBuf> {
-    println!("[Init] Starting rootless-aware rootfs preparation for: {container_id}");
+/// Reads `linux.rootfsPropagation` out of `config.json` and maps it to the
+/// `MsFlags` Phase 1 should remount `/` with, always `MS_REC`'d: `"shared"`
+/// -> `MS_SHARED`, `"private"` -> `MS_PRIVATE`, `"unbindable"` -> `MS_UNBINDABLE`,
+/// `"slave"`/unset -> `MS_SLAVE` (the OCI default when the field is absent).
+fn get_rootfs_propagation(config: &serde_json::Value) -> Result<MsFlags> {
+    let propagation = config
+        .get("linux")
+        .and_then(|linux| linux.get("rootfsPropagation"))
+        .and_then(|v| v.as_str());
+
+    let flag = match propagation {
+        Some("shared") => MsFlags::MS_SHARED,
+        Some("private") => MsFlags::MS_PRIVATE,
+        Some("unbindable") => MsFlags::MS_UNBINDABLE,
+        Some("slave") | None => MsFlags::MS_SLAVE,
+        Some(other) => {
+            return Err(anyhow::anyhow!("Unknown rootfsPropagation value: {other}"));
+        }
+    };
+
+    Ok(flag | MsFlags::MS_REC)
+}
+
+/// Reads the `mounts` array out of the same `config.json` [`get_rootfs`]
+/// already reads, deserializing each entry as [`crate::config::Mount`]. An
+/// absent array (rather than a malformed one) is treated as "no extra
+/// mounts", since `mounts` is optional in the OCI runtime spec.
+fn get_configured_mounts(config: &serde_json::Value) -> Result<Vec<crate::config::Mount>> {
+    match config.get("mounts") {
+        Some(mounts) => serde_json::from_value(mounts.clone())
+            .context("Failed to parse mounts array from config.json"),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Splits a mount's `options` entries into the `MsFlags` they map to, whether
+/// `rbind` asked for a recursive bind mount, the leftover options (for the
+/// caller to join into the mount's data string), and any propagation option (`private`/`shared`/`slave`/
+/// `unbindable`, plus their `r`-prefixed recursive variants) as a separate
+/// `MsFlags` - propagation is applied via its own flag-only remount, not
+/// folded into the main mount's `data` string.
+fn parse_mount_options(options: &[String]) -> (MsFlags, bool, Vec<String>, Option<MsFlags>) {
+    let mut flags = MsFlags::empty();
+    let mut recursive = false;
+    let mut data_opts = Vec::new();
+    let mut propagation = None;
+
+    for option in options {
+        match option.as_str() {
+            "nosuid" => flags |= MsFlags::MS_NOSUID,
+            "nodev" => flags |= MsFlags::MS_NODEV,
+            "noexec" => flags |= MsFlags::MS_NOEXEC,
+            "ro" => flags |= MsFlags::MS_RDONLY,
+            "relatime" => flags |= MsFlags::MS_RELATIME,
+            "bind" => flags |= MsFlags::MS_BIND,
+            "rbind" => {
+                flags |= MsFlags::MS_BIND;
+                recursive = true;
+            }
+            "private" => propagation = Some(MsFlags::MS_PRIVATE),
+            "rprivate" => propagation = Some(MsFlags::MS_PRIVATE | MsFlags::MS_REC),
+            "shared" => propagation = Some(MsFlags::MS_SHARED),
+            "rshared" => propagation = Some(MsFlags::MS_SHARED | MsFlags::MS_REC),
+            "slave" => propagation = Some(MsFlags::MS_SLAVE),
+            "rslave" => propagation = Some(MsFlags::MS_SLAVE | MsFlags::MS_REC),
+            "unbindable" => propagation = Some(MsFlags::MS_UNBINDABLE),
+            "runbindable" => propagation = Some(MsFlags::MS_UNBINDABLE | MsFlags::MS_REC),
+            other => data_opts.push(other.to_string()),
+        }
+    }
+
+    (flags, recursive, data_opts, propagation)
+}
+
+/// Resolves a mount's `destination` against `rootfs`, rejecting any `..`
+/// component so a malicious (or buggy) bundle can't mount outside the
+/// container root.
+fn resolve_mount_destination(rootfs: &Path, destination: &Path) -> Result<PathBuf> {
+    if destination
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err(anyhow::anyhow!(
+            "Mount destination {} contains a path-traversal component",
+            destination.display()
+        ));
+    }
+
+    let relative = destination.strip_prefix("/").unwrap_or(destination);
+    Ok(rootfs.join(relative))
+}
+
+/// Applies one `config.json` mount entry: resolves and creates its
+/// destination under `rootfs`, translates its `options` into `MsFlags` plus
+/// a leftover data string, canonicalizes the source for bind mounts, mounts
+/// it, then applies any propagation option as a separate flag-only remount.
+fn apply_mount_entry(rootfs: &Path, entry: &crate::config::Mount) -> Result<()> {
+    let destination = resolve_mount_destination(rootfs, &entry.destination)?;
+
+    let (mut flags, recursive, data_opts, propagation) = parse_mount_options(&entry.options);
+    if recursive {
+        flags |= MsFlags::MS_REC;
+    }
+    let data = (!data_opts.is_empty()).then(|| data_opts.join(","));
+
+    let is_bind = entry.fs_type == "bind" || flags.contains(MsFlags::MS_BIND);
+    let (source, fs_type) = if is_bind {
+        let canonical = fs::canonicalize(&entry.source)
+            .with_context(|| format!("Failed to canonicalize bind source {}", entry.source))?;
+        (canonical, None)
+    } else {
+        (PathBuf::from(&entry.source), Some(entry.fs_type.as_str()))
+    };
+
+    // A bind source that's a regular file (e.g. `/etc/resolv.conf`) needs a
+    // regular file at the destination - `mount` fails with ENOTDIR if we
+    // create a directory there instead, which `create_dir_all` would do
+    // unconditionally.
+    if is_bind && source.is_file() {
+        if let Some(parent) = destination.parent() {
+            fs::create_dir_all(parent).with_context(|| {
+                format!("Failed to create mount destination parent {}", parent.display())
+            })?;
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(&destination)
+            .with_context(|| format!("Failed to create mount destination {}", destination.display()))?;
+    } else {
+        fs::create_dir_all(&destination)
+            .with_context(|| format!("Failed to create mount destination {}", destination.display()))?;
+    }
+
+    mount(Some(&source), &destination, fs_type, flags, data.as_deref()).with_context(|| {
+        format!(
+            "Failed to mount {} -> {}",
+            source.display(),
+            destination.display()
+        )
+    })?;
+
+    if let Some(propagation_flags) = propagation {
+        mount(
+            None::<&str>,
+            &destination,
+            None::<&str>,
+            propagation_flags,
+            None::<&str>,
+        )
+        .with_context(|| format!("Failed to set mount propagation on {}", destination.display()))?;
+    }
+
+    println!(
+        "[Mount] Mounted {} -> {}",
+        entry.source,
+        destination.display()
+    );
+    Ok(())
+}
+
+/// Applies every entry of `config.json`'s `mounts` array on top of the
+/// baseline proc/sys/dev mounts, in order, the way youki/systemd-nspawn
+/// consume a custom-mount list.
+fn apply_configured_mounts(rootfs: &Path, mounts: &[crate::config::Mount]) -> Result<()> {
+    for entry in mounts {
+        apply_mount_entry(rootfs, entry)
+            .with_context(|| format!("Failed to apply configured mount {}", entry.destination.display()))?;
+    }
+    Ok(())
+}
+
+/// Reads `root.readonly` out of `config.json`, defaulting to `false` when
+/// absent (matches [`crate::config::Root`]'s own `#[serde(default)]`).
+fn get_root_readonly(config: &serde_json::Value) -> Result<bool> {
+    Ok(config
+        .get("root")
+        .and_then(|root| root.get("readonly"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false))
+}
+
+/// Reads `root.layers` out of `config.json` (matches [`crate::config::Root`]'s
+/// own `#[serde(default)]`) - extra lowerdirs to stack under the rootfs via
+/// overlayfs. Empty when absent, which keeps [`prepare_rootfs`] on the plain
+/// self-bind-mount path.
+fn get_root_layers(config: &serde_json::Value) -> Result<Vec<PathBuf>> {
+    match config.get("root").and_then(|root| root.get("layers")) {
+        Some(layers) => serde_json::from_value::<Vec<String>>(layers.clone())
+            .map(|paths| paths.into_iter().map(PathBuf::from).collect())
+            .context("Failed to parse root.layers from config.json"),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Mounts `rootfs` itself as a stacked overlayfs view of `layers` (highest
+/// priority first), instead of the plain self-bind-mount [`prepare_rootfs`]
+/// otherwise does - both make `rootfs` its own mountpoint, which is all
+/// `pivot_root` requires. A `readonly` root gets a plain `lowerdir=` mount
+/// with no upper layer; otherwise an upperdir/workdir pair is created
+/// alongside the container's other runtime state so writes land there.
+fn mount_overlay_rootfs(container_id: &str, rootfs: &Path, layers: &[PathBuf], readonly: bool) -> Result<()> {
+    let lowerdir = build_lowerdir(layers)?;
+
+    let data = if readonly {
+        format!("lowerdir={lowerdir}")
+    } else {
+        let overlay_dir = PathBuf::from(format!("/run/container/{container_id}/overlay"));
+        let upper_dir = overlay_dir.join("upper");
+        let work_dir = overlay_dir.join("work");
+        fs::create_dir_all(&upper_dir).context("Failed to create overlay upperdir")?;
+        fs::create_dir_all(&work_dir).context("Failed to create overlay workdir")?;
+        format!(
+            "lowerdir={lowerdir},upperdir={},workdir={}",
+            upper_dir.display(),
+            work_dir.display()
+        )
+    };
+
+    mount(
+        Some("overlay"),
+        rootfs,
+        Some("overlay"),
+        MsFlags::empty(),
+        Some(data.as_str()),
+    )
+    .context("Failed to mount stacked overlayfs onto rootfs")?;
+
+    Ok(())
+}
+
+/// Joins `layers` into an overlayfs `lowerdir=` value (highest-priority
+/// first), rejecting the whole set if any layer doesn't exist - otherwise a
+/// typo'd or missing layer path in `root.layers` surfaces as `mount(2)`'s raw
+/// `ENOENT`/`EINVAL` instead of pointing at which layer was bad.
+fn build_lowerdir(layers: &[PathBuf]) -> Result<String> {
+    for (index, layer) in layers.iter().enumerate() {
+        if !layer.exists() {
+            return Err(anyhow::anyhow!(
+                "root.layers[{index}] does not exist: {}",
+                layer.display()
+            ));
+        }
+    }
+
+    Ok(layers
+        .iter()
+        .map(|p| p.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+/// Kernel paths that expose host state and have no business being visible
+/// inside a container, mirroring systemd's `ProtectKernelTunables`. Overridden
+/// wholesale by `linux.maskedPaths` when present.
+pub const DEFAULT_MASKED_PATHS: &[&str] = &[
+    "/proc/kcore",
+    "/proc/latency_stats",
+    "/proc/timer_stats",
+    "/proc/sched_debug",
+    "/proc/sysrq-trigger",
+    "/sys/firmware",
+];
+
+/// Paths that should remain visible but not writable, mirroring systemd's
+/// `ProtectSystem`. Overridden wholesale by `linux.readonlyPaths` when present.
+pub const DEFAULT_READONLY_PATHS: &[&str] = &[
+    "/proc/asound",
+    "/proc/bus",
+    "/proc/fs",
+    "/proc/irq",
+    "/proc/sys",
+];
+
+/// Reads `linux.<field>` out of `config.json` as a string array, falling
+/// back to `default` when the field is absent.
+fn get_path_list(config: &serde_json::Value, field: &str, default: &[&str]) -> Result<Vec<String>> {
+    match config.get("linux").and_then(|linux| linux.get(field)) {
+        Some(value) => serde_json::from_value(value.clone())
+            .with_context(|| format!("Failed to parse linux.{field} from config.json")),
+        None => Ok(default.iter().map(|s| s.to_string()).collect()),
+    }
+}
+
+fn get_masked_paths(config: &serde_json::Value) -> Result<Vec<String>> {
+    get_path_list(config, "maskedPaths", DEFAULT_MASKED_PATHS)
+}
+
+fn get_readonly_paths(config: &serde_json::Value) -> Result<Vec<String>> {
+    get_path_list(config, "readonlyPaths", DEFAULT_READONLY_PATHS)
+}
+
+/// Masks one path: a directory gets an empty, inaccessible `tmpfs` mounted
+/// over it, a regular file gets `/dev/null` bind-mounted over it so it reads
+/// as empty. Paths that don't exist in this rootfs are skipped rather than
+/// treated as an error, since the default list covers entries that aren't
+/// present on every kernel (e.g. `/proc/sched_debug` without `CONFIG_SCHED_DEBUG`).
+fn mask_path(path: &str) -> Result<()> {
+    let target = Path::new(path);
+    if !target.exists() {
+        return Ok(());
+    }
+
+    if target.is_dir() {
+        mount(
+            Some("tmpfs"),
+            target,
+            Some("tmpfs"),
+            MsFlags::empty(),
+            Some("size=0k,mode=000"),
+        )
+        .with_context(|| format!("Failed to mask directory {path}"))?;
+    } else {
+        mount(
+            Some("/dev/null"),
+            target,
+            None::<&str>,
+            MsFlags::MS_BIND,
+            None::<&str>,
+        )
+        .with_context(|| format!("Failed to mask file {path}"))?;
+    }
+
+    Ok(())
+}
+
+/// Masks every path in `paths`, making it unreadable from inside the
+/// container. Intended to run after `pivot_root`, once `paths` refer to the
+/// container's own view of `/proc` and `/sys`.
+fn mask_paths(paths: &[&str]) -> Result<()> {
+    for path in paths {
+        mask_path(path)?;
+    }
+    Ok(())
+}
+
+/// Makes one path read-only by bind-mounting it onto itself and then
+/// remounting that bind read-only - a plain `MS_RDONLY` mount fails on an
+/// existing mount point, so the self-bind is required. Missing paths are
+/// skipped, same reasoning as [`mask_path`].
+fn readonly_path(path: &str) -> Result<()> {
+    let target = Path::new(path);
+    if !target.exists() {
+        return Ok(());
+    }
 
-    // Phase 1: Reset mount propagation to prevent host contamination
     mount(
+        Some(target),
+        target,
         None::<&str>,
-        "/",
+        MsFlags::MS_BIND | MsFlags::MS_REC,
         None::<&str>,
-        MsFlags::MS_REC | MsFlags::MS_PRIVATE,
+    )
+    .with_context(|| format!("Failed to bind mount {path} onto itself"))?;
+
+    mount(
+        None::<&str>,
+        target,
+        None::<&str>,
+        MsFlags::MS_REMOUNT | MsFlags::MS_BIND | MsFlags::MS_RDONLY,
         None::<&str>,
     )
-    .context("Failed to make root mount tree private")?;
+    .with_context(|| format!("Failed to remount {path} read-only"))?;
+
+    Ok(())
+}
+
+/// Makes every path in `paths` read-only. Intended to run after `pivot_root`,
+/// same as [`mask_paths`].
+fn readonly_paths(paths: &[&str]) -> Result<()> {
+    for path in paths {
+        readonly_path(path)?;
+    }
+    Ok(())
+}
+
+pub fn prepare_rootfs(container_id: &str, _config: &crate::process::Config) -> Result<PathBuf> {
+    println!("[Init] Starting rootless-aware rootfs preparation for: {container_id}");
 
     if container_id.contains("..") || container_id.contains('/') {
         return Err(anyhow::anyhow!("Invalid container_id: {container_id}"));
     }
 
-    let (rootfs, old_root) = get_rootfs(container_id)?;
-    println!("[Init] Rootfs: {rootfs:?}, Old root: {old_root:?}");
-
-    // Phase 2: Bind mount rootfs to itself (required for pivot_root)
+    // Read and parse config.json once; every Phase below pulls its fields out
+    // of this same `Value` instead of re-reading and re-parsing the file.
+    let config_path = PathBuf::from(format!("/run/container/{container_id}/config.json"));
+    let config_content = fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config.json for {container_id}"))?;
+    let config: serde_json::Value = serde_json::from_str(&config_content)
+        .with_context(|| format!("Failed to parse config.json for {container_id}"))?;
+
+    // Phase 1: Set mount propagation per `linux.rootfsPropagation`, falling
+    // back to slave (not private) when unset so the container can still see
+    // mount events the host makes afterwards (e.g. a dynamically attached
+    // volume), unless the bundle opts out of that explicitly.
+    let propagation_flags = get_rootfs_propagation(&config)
+        .context("Failed to read rootfsPropagation from config.json")?;
+    let root_readonly = get_root_readonly(&config)
+        .context("Failed to read root.readonly from config.json")?;
+    let masked_paths = get_masked_paths(&config)
+        .context("Failed to read linux.maskedPaths from config.json")?;
+    let readonly_path_list = get_readonly_paths(&config)
+        .context("Failed to read linux.readonlyPaths from config.json")?;
     mount(
-        Some(&rootfs),
-        &rootfs,
         None::<&str>,
-        MsFlags::MS_BIND | MsFlags::MS_REC,
+        "/",
+        None::<&str>,
+        propagation_flags,
         None::<&str>,
     )
-    .context("Failed to bind mount rootfs")?;
+    .context("Failed to set root mount tree propagation")?;
+
+    let (rootfs, old_root) = get_rootfs(container_id, &config)?;
+    println!("[Init] Rootfs: {rootfs:?}, Old root: {old_root:?}");
+
+    // Phase 2: Make rootfs its own mountpoint (required for pivot_root) -
+    // either a stacked overlayfs when `root.layers` names extra lowerdirs, or
+    // a plain self-bind mount otherwise.
+    let root_layers = get_root_layers(&config)
+        .context("Failed to read root.layers from config.json")?;
+    if root_layers.is_empty() {
+        mount(
+            Some(&rootfs),
+            &rootfs,
+            None::<&str>,
+            MsFlags::MS_BIND | MsFlags::MS_REC,
+            None::<&str>,
+        )
+        .context("Failed to bind mount rootfs")?;
+    } else {
+        mount_overlay_rootfs(container_id, &rootfs, &root_layers, root_readonly)
+            .context("Failed to mount stacked overlayfs rootfs")?;
+    }
 
     // Phase 3: Mount pseudo-filesystems with rootless-aware strategies
  
@@ -75,22 +475,22 @@ pub fn prepare_rootfs(container_id: &str) -> Result<PathBuf> {
 
     if !proc_result || !sys_result || !dev_result {
 
-        let _ = umount2(&rootfs, MntFlags::MNT_DETACH);
-        
-        if proc_result {
-            let _ = umount2(&rootfs.join("proc"), MntFlags::MNT_DETACH);
-        }
+        // Tear down whatever got mounted under `rootfs` (including any
+        // submounts proc/sys/dev setup left behind, e.g. bind-mounted
+        // /dev/pts) rather than guessing at the three fixed paths.
+        let _ = unmount_subtree(&rootfs);
 
-        if sys_result {
-            let _ = umount2(&rootfs.join("sys"), MntFlags::MNT_DETACH);
-        }
+        return Err(anyhow::anyhow!("Failed to mount proc : {proc_result} \n sys : {sys_result} \n dev : {dev_result}"))
 
-        if dev_result {
-            let _ = umount2(&rootfs.join("dev"), MntFlags::MNT_DETACH);
-        }
+    }
 
-        return Err(anyhow::anyhow!("Failed to mount proc : {proc_result} \n sys : {sys_result} \n dev : {dev_result}"))
- 
+    // Phase 3.5: Apply any declarative mounts from config.json's `mounts`
+    // array (tmpfs, bind mounts, etc.) on top of the baseline above.
+    let configured_mounts = get_configured_mounts(&config)
+        .context("Failed to read configured mounts from config.json")?;
+    if let Err(e) = apply_configured_mounts(&rootfs, &configured_mounts) {
+        let _ = unmount_subtree(&rootfs);
+        return Err(e);
     }
 
     // Phase 4: Switch to container filesystem
@@ -106,20 +506,38 @@ pub fn prepare_rootfs(container_id: &str) -> Result<PathBuf> {
 
     if let Err(e) = chdir_result {
 
-        let _ = umount2(&rootfs, MntFlags::MNT_DETACH);
-        let _ = umount2(&rootfs.join("proc"), MntFlags::MNT_DETACH);
-        let _ = umount2(&rootfs.join("sys"), MntFlags::MNT_DETACH);
-        let _ = umount2(&rootfs.join("dev"), MntFlags::MNT_DETACH);
- 
+        let _ = unmount_subtree(&rootfs);
+
         let _ = fs::remove_dir_all(&rootfs);
         let _ = fs::remove_dir_all(&old_root);
- 
+
         return Err(e).context("Failed to change the root dir, unmounted complete rootfs and removed rootfs.")
     };
 
+    // Phase 4.5: Mask and lock down sensitive paths now that `/proc` and
+    // `/sys` refer to the container's own mounts, then clean up the old
+    // root before (optionally) making the whole rootfs read-only -
+    // `cleanup_old_root` itself needs write access to unmount and remove
+    // `old_root`.
+    let masked_refs: Vec<&str> = masked_paths.iter().map(String::as_str).collect();
+    let readonly_refs: Vec<&str> = readonly_path_list.iter().map(String::as_str).collect();
+    mask_paths(&masked_refs).context("Failed to mask kernel paths")?;
+    readonly_paths(&readonly_refs).context("Failed to apply read-only paths")?;
+
     // Phase 5: Clean up old root
     cleanup_old_root()?;
 
+    if root_readonly {
+        mount(
+            None::<&str>,
+            "/",
+            None::<&str>,
+            MsFlags::MS_REMOUNT | MsFlags::MS_BIND | MsFlags::MS_RDONLY,
+            None::<&str>,
+        )
+        .context("Failed to remount rootfs read-only")?;
+    }
+
     println!("[Init] Rootless container filesystem ready");
     Ok(PathBuf::from("/"))
 }
@@ -357,6 +775,8 @@ fn create_device_nodes(dev_path: &Path) -> Result<()> {
     let essential_devices = [
         ("null", 1u32, 3u32, 0o666),
         ("zero", 1u32, 5u32, 0o666),
+        ("full", 1u32, 7u32, 0o666),
+        ("random", 1u32, 8u32, 0o666),
         ("urandom", 1u32, 9u32, 0o666),
     ];
 
@@ -375,6 +795,11 @@ fn create_device_nodes(dev_path: &Path) -> Result<()> {
     Ok(())
 }
 
+/// Host device nodes bind-mounted onto this rootless `/dev` when `mknod` is
+/// denied, same set as [`create_device_nodes`] plus `tty`, which has no
+/// fixed major/minor worth `mknod`-ing ourselves.
+const BIND_MOUNTED_DEVICES: &[&str] = &["null", "zero", "full", "random", "urandom", "tty"];
+
 fn create_rootless_dev_structure(dev_path: &Path) -> Result<()> {
     println!("[Init] Creating rootless-compatible /dev structure");
 
@@ -383,27 +808,94 @@ fn create_rootless_dev_structure(dev_path: &Path) -> Result<()> {
         fs::create_dir_all(dev_path.join(dir))?;
     }
 
-    let _devices = [
-        ("null", ""),
-        ("zero", ""),
-        ("urandom", "random data placeholder"),
-        ("random", "random data placeholder"),
-        ("tty", ""),
-    ];
-
-    for (name, content) in &_devices {
-        let device_path = dev_path.join(name);
-        fs::write(&device_path, content)
-            .with_context(|| format!("Failed to create placeholder {name}"))?;
-        println!("[Mount] Created placeholder: /dev/{name}");
+    for name in BIND_MOUNTED_DEVICES {
+        bind_mount_host_device(dev_path, name)
+            .with_context(|| format!("Failed to bind mount host device {name}"))?;
     }
 
+    mount_devpts(dev_path).context("Failed to set up /dev/pts")?;
+    mount_dev_shm(dev_path).context("Failed to set up /dev/shm")?;
+
     create_dev_symlinks(dev_path)?;
 
     println!("[Mount] Rootless /dev structure complete");
     Ok(())
 }
 
+/// Bind-mounts the host's `/dev/<name>` onto an empty file at
+/// `dev_path/<name>`, so the container sees a fully working device instead
+/// of a placeholder regular file that breaks any program trying to open it
+/// (e.g. `/dev/full` returning `ENOSPC` on write, `/dev/urandom` actually
+/// producing randomness).
+fn bind_mount_host_device(dev_path: &Path, name: &str) -> Result<()> {
+    let host_device = PathBuf::from("/dev").join(name);
+    let target = dev_path.join(name);
+
+    fs::write(&target, "").with_context(|| format!("Failed to create bind target for {name}"))?;
+
+    mount(
+        Some(&host_device),
+        &target,
+        None::<&str>,
+        MsFlags::MS_BIND,
+        None::<&str>,
+    )
+    .with_context(|| format!("Failed to bind mount {}", host_device.display()))?;
+
+    println!("[Mount] Bind mounted host /dev/{name}");
+    Ok(())
+}
+
+/// Mounts a `devpts` filesystem at `dev_path/pts` for proper pty allocation,
+/// falling back to bind mounting the host's `/dev/pts` when the container
+/// lacks the privileges (e.g. `CAP_SYS_ADMIN` in its own user namespace) to
+/// mount a fresh instance.
+fn mount_devpts(dev_path: &Path) -> Result<()> {
+    let pts_path = dev_path.join("pts");
+    fs::create_dir_all(&pts_path)?;
+
+    let devpts_result = mount(
+        Some("devpts"),
+        &pts_path,
+        Some("devpts"),
+        MsFlags::MS_NOSUID | MsFlags::MS_NOEXEC,
+        Some("newinstance,ptmxmode=0666,mode=0620"),
+    );
+
+    if devpts_result.is_err() {
+        mount(
+            Some("/dev/pts"),
+            &pts_path,
+            None::<&str>,
+            MsFlags::MS_BIND | MsFlags::MS_REC,
+            None::<&str>,
+        )
+        .context("Both devpts mount and bind mount of host /dev/pts failed")?;
+        println!("[Mount] Bind mounted host /dev/pts (devpts mount unavailable)");
+    } else {
+        println!("[Mount] Mounted devpts at /dev/pts");
+    }
+
+    Ok(())
+}
+
+fn mount_dev_shm(dev_path: &Path) -> Result<()> {
+    let shm_path = dev_path.join("shm");
+    fs::create_dir_all(&shm_path)?;
+
+    mount(
+        Some("tmpfs"),
+        &shm_path,
+        Some("tmpfs"),
+        MsFlags::MS_NOSUID | MsFlags::MS_NODEV,
+        Some("mode=1777,size=64k"),
+    )
+    .context("Failed to mount tmpfs for /dev/shm")?;
+
+    println!("[Mount] Mounted tmpfs at /dev/shm");
+    Ok(())
+}
+
 fn create_dev_symlinks(dev_path: &Path) -> Result<()> {
     use std::os::unix::fs::symlink;
 
@@ -412,6 +904,7 @@ fn create_dev_symlinks(dev_path: &Path) -> Result<()> {
         ("stdin", "/proc/self/fd/0"),
         ("stdout", "/proc/self/fd/1"),
         ("stderr", "/proc/self/fd/2"),
+        ("ptmx", "pts/ptmx"),
     ];
 
     for (link_name, target) in &symlinks {
@@ -425,23 +918,231 @@ fn create_dev_symlinks(dev_path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn cleanup_old_root() -> Result<()> {
-    println!("[Init] Cleaning up old root");
+/// One entry of `/proc/self/mountinfo`, trimmed to the fields cleanup code
+/// actually needs. See `proc_pid_mountinfo(5)` for the full line format.
+#[derive(Debug, Clone)]
+struct MountInfo {
+    #[allow(dead_code)]
+    mount_id: u32,
+    #[allow(dead_code)]
+    parent_id: u32,
+    mount_point: PathBuf,
+    #[allow(dead_code)]
+    fs_type: String,
+    #[allow(dead_code)]
+    mount_source: String,
+}
 
-    match umount2("/old_root", MntFlags::MNT_DETACH) {
-        Ok(_) => println!("[Init] Old root unmounted"),
-        Err(e) => {
-            println!("[Init] Warning: Failed to unmount old root: {e}");
-            if let Err(new) = umount2("/old_root", MntFlags::MNT_DETACH | MntFlags::MNT_FORCE) {
-                return Err(new).context("Failed to unmount old root : {new}");
-            } else {
-                match fs::remove_dir_all("/old_root") {
-                    Ok(_) => println!("[Init] Old root directory removed"),
-                    Err(e) => println!("[Init] Warning: Failed to remove old root: {e}"),
-                }
+/// Undoes mountinfo's octal escaping (`\040` for space, `\011` for tab,
+/// `\012` for newline, `\134` for backslash) in a single field.
+fn unescape_mountinfo_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() {
+            if let Ok(code) = u8::from_str_radix(
+                std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap_or_default(),
+                8,
+            ) {
+                out.push(code);
+                i += 4;
+                continue;
             }
         }
+        out.push(bytes[i]);
+        i += 1;
     }
-    
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Parses `/proc/self/mountinfo` into one [`MountInfo`] per line.
+fn parse_mountinfo() -> Result<Vec<MountInfo>> {
+    let content = fs::read_to_string("/proc/self/mountinfo")
+        .context("Failed to read /proc/self/mountinfo")?;
+    parse_mountinfo_str(&content)
+}
+
+/// Does the actual line parsing for [`parse_mountinfo`], split out so tests
+/// can feed it fixture content instead of the real `/proc/self/mountinfo`.
+/// Each line has a variable number of optional fields before a literal `-`
+/// separator; we only need what comes before it (mount/parent IDs, mount
+/// point) and the first two fields after it (fs type, mount source).
+fn parse_mountinfo_str(content: &str) -> Result<Vec<MountInfo>> {
+    let mut entries = Vec::new();
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (pre, post) = line
+            .split_once(" - ")
+            .with_context(|| format!("Malformed mountinfo line (no ' - ' separator): {line}"))?;
+
+        let pre_fields: Vec<&str> = pre.split_whitespace().collect();
+        if pre_fields.len() < 5 {
+            return Err(anyhow::anyhow!("Malformed mountinfo line (too few fields): {line}"));
+        }
+        let post_fields: Vec<&str> = post.split_whitespace().collect();
+        if post_fields.len() < 2 {
+            return Err(anyhow::anyhow!("Malformed mountinfo line (missing fs type/source): {line}"));
+        }
+
+        entries.push(MountInfo {
+            mount_id: pre_fields[0]
+                .parse()
+                .with_context(|| format!("Invalid mount_id in: {line}"))?,
+            parent_id: pre_fields[1]
+                .parse()
+                .with_context(|| format!("Invalid parent_id in: {line}"))?,
+            mount_point: PathBuf::from(unescape_mountinfo_field(pre_fields[4])),
+            fs_type: unescape_mountinfo_field(post_fields[0]),
+            mount_source: unescape_mountinfo_field(post_fields[1]),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Unmounts every mount point at or under `prefix`, deepest path first, so a
+/// submount is always detached before the mount it sits on. Reads the live
+/// mount table via [`parse_mountinfo`] instead of guessing at fixed paths,
+/// so bind submounts, overlays, and tmpfs mounts layered on top of `prefix`
+/// are caught rather than leaked into the user's `/run/user/<uid>` tree.
+fn unmount_subtree(prefix: &Path) -> Result<()> {
+    let mut mount_points: Vec<PathBuf> = parse_mountinfo()?
+        .into_iter()
+        .filter(|m| m.mount_point == prefix || m.mount_point.starts_with(prefix))
+        .map(|m| m.mount_point)
+        .collect();
+
+    mount_points.sort_by_key(|p| std::cmp::Reverse(p.components().count()));
+
+    for mount_point in mount_points {
+        match umount2(&mount_point, MntFlags::MNT_DETACH) {
+            Ok(_) => println!("[Cleanup] Unmounted {}", mount_point.display()),
+            Err(e) => println!("[Cleanup] Warning: Failed to unmount {}: {e}", mount_point.display()),
+        }
+    }
+
+    Ok(())
+}
+
+fn cleanup_old_root() -> Result<()> {
+    println!("[Init] Cleaning up old root");
+
+    unmount_subtree(Path::new("/old_root")).context("Failed to unmount old root subtree")?;
+
+    match fs::remove_dir_all("/old_root") {
+        Ok(_) => println!("[Init] Old root directory removed"),
+        Err(e) => println!("[Init] Warning: Failed to remove old root: {e}"),
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod mountinfo_tests {
+    use super::*;
+
+    #[test]
+    fn test_unescape_mountinfo_field_handles_known_escapes() {
+        assert_eq!(unescape_mountinfo_field("a\\040b"), "a b");
+        assert_eq!(unescape_mountinfo_field("a\\011b"), "a\tb");
+        assert_eq!(unescape_mountinfo_field("a\\012b"), "a\nb");
+        assert_eq!(unescape_mountinfo_field("a\\134b"), "a\\b");
+    }
+
+    #[test]
+    fn test_unescape_mountinfo_field_passes_through_plain_text() {
+        assert_eq!(unescape_mountinfo_field("/var/lib/container/rootfs"), "/var/lib/container/rootfs");
+        assert_eq!(unescape_mountinfo_field(""), "");
+    }
+
+    #[test]
+    fn test_unescape_mountinfo_field_ignores_trailing_backslash() {
+        // Not enough bytes left for a full `\NNN` escape - left as-is.
+        assert_eq!(unescape_mountinfo_field("a\\"), "a\\");
+    }
+
+    #[test]
+    fn test_parse_mountinfo_str_basic_line() {
+        let line = "25 30 0:23 / / rw,relatime shared:1 - ext4 /dev/sda1 rw,errors=remount-ro";
+        let entries = parse_mountinfo_str(line).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].mount_id, 25);
+        assert_eq!(entries[0].parent_id, 30);
+        assert_eq!(entries[0].mount_point, PathBuf::from("/"));
+        assert_eq!(entries[0].fs_type, "ext4");
+        assert_eq!(entries[0].mount_source, "/dev/sda1");
+    }
+
+    #[test]
+    fn test_parse_mountinfo_str_unescapes_mount_point() {
+        let line = "36 25 0:31 / /var/lib/my\\040container rw shared:2 - tmpfs tmpfs rw";
+        let entries = parse_mountinfo_str(line).unwrap();
+        assert_eq!(entries[0].mount_point, PathBuf::from("/var/lib/my container"));
+    }
+
+    #[test]
+    fn test_parse_mountinfo_str_skips_blank_lines() {
+        let content = "\n25 30 0:23 / / rw - ext4 /dev/sda1 rw\n\n";
+        let entries = parse_mountinfo_str(content).unwrap();
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_mountinfo_str_multiple_lines() {
+        let content = "\
+25 30 0:23 / / rw - ext4 /dev/sda1 rw
+36 25 0:31 / /proc rw - proc proc rw
+37 25 0:32 / /sys rw - sysfs sysfs rw";
+        let entries = parse_mountinfo_str(content).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[1].mount_point, PathBuf::from("/proc"));
+        assert_eq!(entries[2].fs_type, "sysfs");
+    }
+
+    #[test]
+    fn test_parse_mountinfo_str_rejects_missing_separator() {
+        let content = "25 30 0:23 / / rw shared:1 ext4 /dev/sda1 rw";
+        assert!(parse_mountinfo_str(content).is_err());
+    }
+
+    #[test]
+    fn test_parse_mountinfo_str_rejects_too_few_pre_fields() {
+        let content = "25 30 0:23 - ext4 /dev/sda1 rw";
+        assert!(parse_mountinfo_str(content).is_err());
+    }
+}
+
+#[cfg(test)]
+mod overlay_tests {
+    use super::*;
+
+    #[test]
+    fn test_build_lowerdir_joins_existing_layers_highest_priority_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let layer_a = tmp.path().join("a");
+        let layer_b = tmp.path().join("b");
+        fs::create_dir_all(&layer_a).unwrap();
+        fs::create_dir_all(&layer_b).unwrap();
+
+        let lowerdir = build_lowerdir(&[layer_a.clone(), layer_b.clone()]).unwrap();
+        assert_eq!(
+            lowerdir,
+            format!("{}:{}", layer_a.display(), layer_b.display())
+        );
+    }
+
+    #[test]
+    fn test_build_lowerdir_rejects_missing_layer() {
+        let tmp = tempfile::tempdir().unwrap();
+        let present = tmp.path().join("present");
+        fs::create_dir_all(&present).unwrap();
+        let missing = tmp.path().join("missing");
+
+        let err = build_lowerdir(&[present, missing.clone()]).unwrap_err();
+        assert!(err.to_string().contains(&missing.display().to_string()));
+    }
+}
@@ -1,8 +1,13 @@
+pub mod api;
+pub mod binary_checker;
+pub mod cgroups;
 pub mod config;
 pub mod config2;
+pub mod devices;
+pub mod networking;
 pub mod seccomp;
 
-pub use config2::{SeccompConfig, SyscallRule, load_config};
+pub use config2::{SeccompConfig, SyscallRule};
 
 pub mod fs;
 pub mod process;
@@ -1,11 +1,31 @@
 use crate::binary_checker::BinaryChecker;
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
+use nix::fcntl::{FlockArg, flock};
+use nix::unistd::Pid;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::net::Ipv4Addr;
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-#[derive(Debug, Clone)]
-pub struct NetworkConfig {
-    pub port_mappings: Vec<PortMapping>,
-    pub command: Vec<String>,
+/// Which networking backend a container uses, persisted on
+/// [`crate::process::ContainerState`] so `delete_container` knows what (if
+/// any) teardown to run. `None` is the default - nothing has historically
+/// called any networking setup, so bundles that don't ask for it keep
+/// getting no networking rather than silently picking one up. `Slirp4netns`
+/// attaches [`setup_slirp_network`] to the container's own pid; `Veth`
+/// instead gives it a persistent address on the shared `bento0` bridge via
+/// [`setup_veth_network`], so containers can reach each other.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkMode {
+    #[default]
+    None,
+    Slirp4netns,
+    Veth,
 }
 
 #[derive(Debug, Clone)]
@@ -22,103 +42,100 @@ pub enum Protocol {
     Udp,
 }
 
-impl NetworkConfig {
-    pub fn new(command: Vec<String>) -> Self {
-        Self {
-            port_mappings: Vec::new(),
-            command,
-        }
-    }
+/// Sends one newline-terminated JSON command to slirp4netns's API socket
+/// and returns the parsed reply, per slirp4netns(1)'s `--api-socket` protocol.
+fn send_slirp_command(api_socket: &str, command: &serde_json::Value) -> Result<serde_json::Value> {
+    let mut stream = UnixStream::connect(api_socket)
+        .with_context(|| format!("Failed to connect to slirp4netns API socket {api_socket}"))?;
 
-    pub fn with_ports(mut self, ports: Vec<PortMapping>) -> Self {
-        self.port_mappings = ports;
-        self
-    }
-}
+    let mut line = serde_json::to_string(command)?;
+    line.push('\n');
+    stream
+        .write_all(line.as_bytes())
+        .context("Failed to send command to slirp4netns API socket")?;
 
-pub fn setup_network(config: &NetworkConfig) -> Result<()> {
-    println!("🚀 Setting up networking...");
-
-    BinaryChecker::validate_required_binaries()?;
-
-    let can_unshare = test_unshare_capability();
-    if !can_unshare {
-        println!("⚠️ Cannot create network namespaces (requires privileges or sysctl settings)");
-        println!("💡 Running command in current namespace...");
-        return run_in_current_namespace(config);
-    }
+    let mut reply_line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut reply_line)
+        .context("Failed to read reply from slirp4netns API socket")?;
 
-    let mut cmd = Command::new("unshare");
-    cmd.args(["--net", "--fork"]);
-    cmd.arg("sh").arg("-c");
-
-    let mut setup_script = String::new();
-    setup_script.push_str(
-        "(slirp4netns --configure --mtu=65520 --disable-host-loopback $$ tap0 &) && sleep 2\n",
-    );
+    serde_json::from_str(&reply_line)
+        .with_context(|| format!("Failed to parse slirp4netns reply: {reply_line}"))
+}
 
-    if !config.port_mappings.is_empty() {
-        println!("⚠️ Port forwarding not yet implemented in direct mode");
-    }
+/// Issues `add_hostfwd` for one mapping and returns the forward's id from
+/// the `{"return":{"id":N}}` reply, needed later to `remove_hostfwd` it.
+fn add_hostfwd(api_socket: &str, mapping: &PortMapping) -> Result<u64> {
+    let proto = match mapping.protocol {
+        Protocol::Tcp => "tcp",
+        Protocol::Udp => "udp",
+    };
 
-    for (i, arg) in config.command.iter().enumerate() {
-        if i > 0 {
-            setup_script.push(' ');
-        }
-        if arg.contains(' ') || arg.contains('"') || arg.contains('\'') {
-            setup_script.push_str(&format!("'{}'", arg.replace('\'', "'\\''")));
-        } else {
-            setup_script.push_str(arg);
+    let command = serde_json::json!({
+        "execute": "add_hostfwd",
+        "arguments": {
+            "proto": proto,
+            "host_addr": mapping.bind_addr,
+            "host_port": mapping.host_port,
+            "guest_port": mapping.container_port,
         }
-    }
-
-    cmd.arg(setup_script);
-    cmd.stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit());
+    });
 
-    println!("🌐 Starting container...");
-    let status = cmd
-        .status()
-        .map_err(|e| anyhow!("Failed to execute: {}", e))?;
-    if !status.success() {
-        return Err(anyhow!("Network setup failed: {}", status));
-    }
-    Ok(())
+    let reply = send_slirp_command(api_socket, &command)?;
+    reply["return"]["id"]
+        .as_u64()
+        .ok_or_else(|| anyhow!("slirp4netns add_hostfwd reply missing id: {reply}"))
 }
 
-fn test_unshare_capability() -> bool {
-    let test_result = Command::new("unshare").args(["--net", "true"]).output();
+fn remove_hostfwd(api_socket: &str, id: u64) -> Result<()> {
+    let command = serde_json::json!({
+        "execute": "remove_hostfwd",
+        "arguments": { "id": id }
+    });
 
-    match test_result {
-        Ok(output) => output.status.success(),
-        Err(_) => false,
+    let reply = send_slirp_command(api_socket, &command)?;
+    if reply.get("return").is_some() {
+        Ok(())
+    } else {
+        Err(anyhow!("slirp4netns remove_hostfwd failed: {reply}"))
     }
 }
 
-fn run_in_current_namespace(config: &NetworkConfig) -> Result<()> {
-    println!("🚀 Executing in current namespace (no isolation)...");
-
-    if !config.port_mappings.is_empty() {
-        println!("⚠️ Port forwarding not available without network namespace");
-    }
+#[allow(dead_code)]
+fn list_hostfwd(api_socket: &str) -> Result<serde_json::Value> {
+    send_slirp_command(api_socket, &serde_json::json!({ "execute": "list_hostfwd" }))
+}
 
-    if config.command.is_empty() {
-        return Err(anyhow!("No command specified"));
+/// Forwards every `PortMapping` through slirp4netns and returns the ids
+/// `remove_hostfwd` needs at teardown, in the same order as `port_mappings`.
+fn apply_port_mappings(api_socket: &str, port_mappings: &[PortMapping]) -> Result<Vec<u64>> {
+    let mut ids = Vec::with_capacity(port_mappings.len());
+    for mapping in port_mappings {
+        let id = add_hostfwd(api_socket, mapping).with_context(|| {
+            format!(
+                "Failed to forward {}:{} -> container:{}",
+                mapping.bind_addr, mapping.host_port, mapping.container_port
+            )
+        })?;
+        println!(
+            "🔀 Forwarding {}:{} -> container:{}",
+            mapping.bind_addr, mapping.host_port, mapping.container_port
+        );
+        ids.push(id);
     }
+    Ok(ids)
+}
 
-    let status = Command::new(&config.command[0])
-        .args(&config.command[1..])
-        .stdin(Stdio::inherit())
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status()
-        .map_err(|e| anyhow!("Failed to execute: {}", e))?;
-
-    if !status.success() {
-        return Err(anyhow!("Command failed: {}", status));
+/// Removes every forward added by [`apply_port_mappings`] before the
+/// namespace (and slirp4netns along with it) goes away. Failures are logged
+/// rather than propagated since the network namespace is tearing down
+/// regardless.
+fn teardown_port_mappings(api_socket: &str, ids: &[u64]) {
+    for &id in ids {
+        if let Err(e) = remove_hostfwd(api_socket, id) {
+            println!("⚠️ Failed to remove port forward {id}: {e}");
+        }
     }
-    Ok(())
 }
 
 pub fn parse_port_mappings(port_str: &str) -> Vec<PortMapping> {
@@ -176,3 +193,383 @@ fn parse_port_and_protocol(port_str: &str) -> (Option<u16>, Protocol) {
         (port_str.parse().ok(), Protocol::Tcp)
     }
 }
+
+/// Bridge shared by every `Veth`-mode container, and the `/24` it owns.
+const BRIDGE_NAME: &str = "bento0";
+const SUBNET_OCTETS: [u8; 3] = [10, 200, 0];
+
+fn bridge_addr() -> Ipv4Addr {
+    Ipv4Addr::new(SUBNET_OCTETS[0], SUBNET_OCTETS[1], SUBNET_OCTETS[2], 1)
+}
+
+/// Gives `container_id`'s init process (`pid`) a persistent, addressable
+/// home on the shared `bento0` bridge: a veth pair with one end moved into
+/// the container's net namespace and addressed from `bento0`'s subnet, a
+/// default route back through the bridge, and a MASQUERADE rule so the
+/// container can still reach the outside world. Two containers set up this
+/// way can reach each other directly over `bento0`, unlike containers set up
+/// by [`setup_slirp_network`], which each get a private slirp4netns network.
+pub fn setup_veth_network(container_id: &str, pid: Pid) -> Result<Ipv4Addr> {
+    BinaryChecker::validate_veth_binaries()?;
+    ensure_bridge()?;
+
+    let host_veth = host_veth_name(container_id);
+    let container_veth = "eth0";
+    let ip = allocate_container_ip(container_id)?;
+    let pid_str = pid.to_string();
+
+    run_ip(&[
+        "link", "add", &host_veth, "type", "veth", "peer", "name", container_veth,
+    ])?;
+    run_ip(&["link", "set", &host_veth, "master", BRIDGE_NAME])?;
+    run_ip(&["link", "set", &host_veth, "up"])?;
+    run_ip(&["link", "set", container_veth, "netns", &pid_str])?;
+
+    run_in_netns(&pid_str, &["link", "set", "lo", "up"])?;
+    run_in_netns(
+        &pid_str,
+        &["addr", "add", &format!("{ip}/24"), "dev", container_veth],
+    )?;
+    run_in_netns(&pid_str, &["link", "set", container_veth, "up"])?;
+    run_in_netns(
+        &pid_str,
+        &["route", "add", "default", "via", &bridge_addr().to_string()],
+    )?;
+
+    ensure_masquerade(&ip)?;
+
+    println!("🔗 Attached container '{container_id}' to {BRIDGE_NAME} as {ip}");
+    Ok(ip)
+}
+
+/// Reverses [`setup_veth_network`]: deleting the host-side veth end tears
+/// down its container-side peer along with it, so this just has to remove
+/// the MASQUERADE rule and free the address back to the pool. Deleting the
+/// veth and removing the rule are best-effort (failures are only logged) so a
+/// partially-set-up or already-gone network doesn't block
+/// `delete_container`; a failure to read or update the address pool is
+/// returned as an error.
+pub fn teardown_veth_network(container_id: &str) -> Result<()> {
+    let host_veth = host_veth_name(container_id);
+    if let Err(e) = run_ip(&["link", "delete", &host_veth]) {
+        println!("⚠️ Failed to delete veth '{host_veth}' for '{container_id}': {e}");
+    }
+
+    if let Some(ip) = allocated_container_ip(container_id)? {
+        if let Err(e) = remove_masquerade(&ip) {
+            println!("⚠️ Failed to remove MASQUERADE rule for {ip}: {e}");
+        }
+    }
+
+    release_container_ip(container_id)
+}
+
+/// Where [`setup_slirp_network`] persists the pid it spawned and the API
+/// socket it's listening on, so a later `bento delete` (a fresh process,
+/// with no memory of either) can find and stop it.
+#[derive(Debug, Serialize, Deserialize)]
+struct SlirpHandle {
+    pid: i32,
+    api_socket: String,
+}
+
+fn slirp_handle_path(container_id: &str) -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    let dir = PathBuf::from(format!("{home}/.local/share/bento/network"));
+    std::fs::create_dir_all(&dir).context("Failed to create bento network state directory")?;
+    Ok(dir.join(format!("slirp-{container_id}.json")))
+}
+
+fn save_slirp_handle(container_id: &str, handle: &SlirpHandle) -> Result<()> {
+    let path = slirp_handle_path(container_id)?;
+    let contents =
+        serde_json::to_string_pretty(handle).context("Failed to serialize slirp4netns handle")?;
+    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Blocks until slirp4netns has created its `--api-socket`, so
+/// [`apply_port_mappings`] doesn't race the child process starting up.
+fn wait_for_slirp_api_socket(api_socket: &str) -> Result<()> {
+    for _ in 0..50 {
+        if Path::new(api_socket).exists() {
+            return Ok(());
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    Err(anyhow!(
+        "Timed out waiting for slirp4netns API socket {api_socket}"
+    ))
+}
+
+/// Attaches `pid` (a container's already-running init process) to a fresh
+/// slirp4netns instance over its net namespace, using slirp4netns's own
+/// `PID TAPNAME` attach mode - `pid` already exists and keeps running after
+/// this returns, so slirp4netns has to be told which namespace to configure
+/// instead of creating (and unsharing into) its own.
+pub fn setup_slirp_network(container_id: &str, pid: Pid, port_mappings: &[PortMapping]) -> Result<()> {
+    BinaryChecker::validate_required_binaries()?;
+
+    let api_socket = format!("/tmp/bento-slirp-api-{container_id}.sock");
+    let _ = std::fs::remove_file(&api_socket); // stale socket from a previous run of this container
+
+    let child = Command::new("slirp4netns")
+        .args([
+            "--configure",
+            "--mtu=65520",
+            "--disable-host-loopback",
+            "--api-socket",
+            &api_socket,
+            &pid.to_string(),
+            "tap0",
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to start slirp4netns")?;
+
+    save_slirp_handle(
+        container_id,
+        &SlirpHandle {
+            pid: child.id() as i32,
+            api_socket: api_socket.clone(),
+        },
+    )?;
+    // slirp4netns outlives this process; teardown finds it again by pid via
+    // the handle file rather than this `Child`, so don't wait() on it here.
+    std::mem::forget(child);
+
+    wait_for_slirp_api_socket(&api_socket)?;
+
+    if !port_mappings.is_empty() {
+        apply_port_mappings(&api_socket, port_mappings)
+            .context("Failed to set up port forwarding via slirp4netns")?;
+    }
+
+    println!("🔗 Attached container '{container_id}' to slirp4netns (PID {pid})");
+    Ok(())
+}
+
+/// Reverses [`setup_slirp_network`]: killing slirp4netns tears down the tap
+/// device and every port forward along with it, so this just needs to find
+/// and stop the right process. Best-effort, like [`teardown_veth_network`],
+/// so a partially-set-up or already-gone network doesn't block
+/// `delete_container`.
+pub fn teardown_slirp_network(container_id: &str) -> Result<()> {
+    let path = slirp_handle_path(container_id)?;
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let handle: SlirpHandle = serde_json::from_str(&contents)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    if let Err(e) = nix::sys::signal::kill(
+        Pid::from_raw(handle.pid),
+        nix::sys::signal::Signal::SIGTERM,
+    ) {
+        println!(
+            "⚠️ Failed to stop slirp4netns (PID {}) for '{container_id}': {e}",
+            handle.pid
+        );
+    }
+
+    let _ = std::fs::remove_file(&handle.api_socket);
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// Interface names are capped at `IFNAMSIZ` (16 bytes including the NUL), so
+/// the host-side veth can't just be named after the container id - hash it
+/// down to something that always fits and still round-trips to a unique
+/// name for teardown.
+fn host_veth_name(container_id: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325; // FNV-1a
+    for byte in container_id.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("veth{:08x}", hash as u32)
+}
+
+fn run_ip(args: &[&str]) -> Result<()> {
+    let status = Command::new("ip")
+        .args(args)
+        .status()
+        .map_err(|e| anyhow!("Failed to run `ip {}`: {e}", args.join(" ")))?;
+    if !status.success() {
+        return Err(anyhow!("`ip {}` failed: {status}", args.join(" ")));
+    }
+    Ok(())
+}
+
+/// Runs `ip <args>` inside `pid`'s namespaces via `nsenter -t <pid> -n`,
+/// since the container-side veth end lives in a namespace we can only reach
+/// by pid (it was never given a name under `/var/run/netns`).
+fn run_in_netns(pid: &str, args: &[&str]) -> Result<()> {
+    let status = Command::new("nsenter")
+        .args(["-t", pid, "-n", "--", "ip"])
+        .args(args)
+        .status()
+        .map_err(|e| anyhow!("Failed to run `ip {}` in PID {pid}'s netns: {e}", args.join(" ")))?;
+    if !status.success() {
+        return Err(anyhow!(
+            "`ip {}` in PID {pid}'s netns failed: {status}",
+            args.join(" ")
+        ));
+    }
+    Ok(())
+}
+
+/// Creates `bento0` and brings it up if it doesn't already exist; a no-op
+/// otherwise, since every container on the host shares the one bridge.
+fn ensure_bridge() -> Result<()> {
+    let exists = Command::new("ip")
+        .args(["link", "show", BRIDGE_NAME])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    if exists {
+        return Ok(());
+    }
+
+    run_ip(&["link", "add", "name", BRIDGE_NAME, "type", "bridge"])?;
+    run_ip(&[
+        "addr",
+        "add",
+        &format!("{}/24", bridge_addr()),
+        "dev",
+        BRIDGE_NAME,
+    ])?;
+    run_ip(&["link", "set", BRIDGE_NAME, "up"])?;
+    Ok(())
+}
+
+/// Adds a MASQUERADE rule for `container_ip`'s egress traffic, skipping it
+/// if one is already installed (e.g. a prior container given the same
+/// address left it behind).
+fn ensure_masquerade(container_ip: &Ipv4Addr) -> Result<()> {
+    let rule_args = ["-s", &format!("{container_ip}/32"), "-j", "MASQUERADE"];
+
+    let already_installed = Command::new("iptables")
+        .args(["-t", "nat", "-C", "POSTROUTING"])
+        .args(rule_args)
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false);
+    if already_installed {
+        return Ok(());
+    }
+
+    let status = Command::new("iptables")
+        .args(["-t", "nat", "-A", "POSTROUTING"])
+        .args(rule_args)
+        .status()
+        .map_err(|e| anyhow!("Failed to run iptables: {e}"))?;
+    if !status.success() {
+        return Err(anyhow!("Failed to install MASQUERADE rule for {container_ip}"));
+    }
+    Ok(())
+}
+
+fn remove_masquerade(container_ip: &Ipv4Addr) -> Result<()> {
+    let rule_args = ["-s", &format!("{container_ip}/32"), "-j", "MASQUERADE"];
+    let status = Command::new("iptables")
+        .args(["-t", "nat", "-D", "POSTROUTING"])
+        .args(rule_args)
+        .status()
+        .map_err(|e| anyhow!("Failed to run iptables: {e}"))?;
+    if !status.success() {
+        return Err(anyhow!("Failed to remove MASQUERADE rule for {container_ip}"));
+    }
+    Ok(())
+}
+
+fn ipam_path() -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    let dir = PathBuf::from(format!("{home}/.local/share/bento/network"));
+    std::fs::create_dir_all(&dir).context("Failed to create bento network state directory")?;
+    Ok(dir.join("ipam.json"))
+}
+
+fn load_allocations() -> Result<HashMap<String, u8>> {
+    let path = ipam_path()?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let contents = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    // `with_ipam_lock` creates the file empty on first use.
+    if contents.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+    serde_json::from_str(&contents).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn save_allocations(allocations: &HashMap<String, u8>) -> Result<()> {
+    let path = ipam_path()?;
+    let contents = serde_json::to_string_pretty(allocations)
+        .context("Failed to serialize bento0 address allocations")?;
+    std::fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Runs `f` over the current allocation map with an exclusive `flock` held on
+/// `ipam.json` for the whole read-modify-write, then persists whatever `f`
+/// left in the map - without this, two concurrent `bento create --network
+/// veth` invocations (or the CLI racing the chunk5-6 daemon) can both read
+/// the same map, pick the same free octet, and stomp each other's write.
+fn with_ipam_lock<T>(f: impl FnOnce(&mut HashMap<String, u8>) -> Result<T>) -> Result<T> {
+    let path = ipam_path()?;
+    let lock_file = OpenOptions::new()
+        .create(true)
+        .read(true)
+        .write(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+
+    flock(&lock_file, FlockArg::LockExclusive)
+        .with_context(|| format!("Failed to lock {}", path.display()))?;
+
+    let mut allocations = load_allocations()?;
+    let result = f(&mut allocations)?;
+    save_allocations(&allocations)?;
+
+    let _ = flock(&lock_file, FlockArg::Unlock);
+    Ok(result)
+}
+
+/// Allocates (or reuses) a `10.200.0.0/24` address for `container_id`, the
+/// pool's bookkeeping persisted to disk since each `bento` invocation is a
+/// fresh process with no shared in-memory state.
+fn allocate_container_ip(container_id: &str) -> Result<Ipv4Addr> {
+    with_ipam_lock(|allocations| {
+        if let Some(&octet) = allocations.get(container_id) {
+            return Ok(octet_to_addr(octet));
+        }
+
+        let used: HashSet<u8> = allocations.values().copied().collect();
+        let octet = (2..254)
+            .find(|o| !used.contains(o))
+            .ok_or_else(|| anyhow!("{BRIDGE_NAME} address pool exhausted"))?;
+
+        allocations.insert(container_id.to_string(), octet);
+        Ok(octet_to_addr(octet))
+    })
+}
+
+fn allocated_container_ip(container_id: &str) -> Result<Option<Ipv4Addr>> {
+    Ok(load_allocations()?.get(container_id).copied().map(octet_to_addr))
+}
+
+fn release_container_ip(container_id: &str) -> Result<()> {
+    with_ipam_lock(|allocations| {
+        allocations.remove(container_id);
+        Ok(())
+    })
+}
+
+fn octet_to_addr(octet: u8) -> Ipv4Addr {
+    Ipv4Addr::new(SUBNET_OCTETS[0], SUBNET_OCTETS[1], SUBNET_OCTETS[2], octet)
+}
@@ -2,18 +2,24 @@
 
 use crate::fs;
 use crate::syscalls::{
-    disable_setgroups_for_child, fork_intermediate, map_user_namespace_rootless,
+    disable_setgroups_for_child, fork_intermediate, join_namespaces, map_user_namespace_rootless,
     unshare_remaining_namespaces, unshare_user_namespace,
 };
 use anyhow::{Context, Result, anyhow};
-use nix::sys::signal::{Signal, kill};
-use nix::sys::stat::Mode;
-use nix::sys::wait::{WaitStatus, waitpid};
-use nix::unistd::{ForkResult, Pid, fork, getpid, mkfifo, pipe, read, write};
+use nix::sys::signal::{Signal, SigHandler, kill};
+use nix::sys::socket::{
+    AddressFamily, Backlog, MsgFlags, SockFlag, SockType, UnixAddr, accept, bind, connect,
+    listen, recv, send, socket,
+};
+use nix::pty::openpty;
+use nix::sys::wait::{WaitPidFlag, WaitStatus, waitpid};
+use nix::unistd::{ForkResult, Pid, Uid, fork, getpid, pipe, read, write};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs as std_fs;
-use std::os::unix::io::{AsRawFd, OwnedFd};
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 // NEW: Add the RootfsPopulationMethod enum
 #[derive(Debug, Clone)]
@@ -22,14 +28,307 @@ pub enum RootfsPopulationMethod {
     BusyBox,
 }
 
+// ============================================================================
+// LIFECYCLE EVENT PROTOCOL
+// ============================================================================
+//
+// Mirrors the `Event::Suspend`/`Event::Exit` serde protocol nbsh's shell uses
+// to hear back from its pipeline processes: each phase transition a
+// container goes through is a typed event rather than an ad hoc status
+// string write, and `state.json` keeps the full, timestamped history instead
+// of only the latest status.
+
+/// A single container lifecycle transition.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum LifecycleEvent {
+    Created,
+    Started,
+    Paused,
+    Resumed,
+    Exited { code: i32 },
+    Signaled { signal: i32 },
+}
+
+impl LifecycleEvent {
+    /// The [`ContainerStatus`] this event puts the container in.
+    fn status(&self) -> ContainerStatus {
+        match self {
+            LifecycleEvent::Created => ContainerStatus::Created,
+            LifecycleEvent::Started | LifecycleEvent::Resumed => ContainerStatus::Running,
+            LifecycleEvent::Paused => ContainerStatus::Paused,
+            LifecycleEvent::Exited { .. } | LifecycleEvent::Signaled { .. } => {
+                ContainerStatus::Stopped
+            }
+        }
+    }
+}
+
+// ============================================================================
+// CONTAINER STATUS STATE MACHINE
+// ============================================================================
+//
+// The single source of truth for what a container is allowed to do next.
+// `ContainerState.status` stores this directly (serialized camelCase)
+// instead of a free-form string, so "running but dead" or other
+// inconsistent states can't round-trip through `state.json` - and every
+// lifecycle command (`start`, `kill`, `delete`, pause/resume) checks the
+// same `can_*` predicates rather than re-deriving its own string match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContainerStatus {
+    /// The orchestrator/bridge/init fork chain is still being built; not yet
+    /// persisted to `state.json` under normal operation.
+    Creating,
+    Created,
+    Running,
+    Paused,
+    Stopped,
+}
+
+impl ContainerStatus {
+    pub fn can_start(&self) -> bool {
+        matches!(self, ContainerStatus::Created)
+    }
+
+    pub fn can_kill(&self) -> bool {
+        matches!(self, ContainerStatus::Running | ContainerStatus::Paused)
+    }
+
+    /// Guards [`delete_container`] - only a fully stopped container can be
+    /// removed, the same `delete <id>` contract OCI runtimes use.
+    pub fn can_delete(&self) -> bool {
+        matches!(self, ContainerStatus::Stopped)
+    }
+
+    pub fn can_pause(&self) -> bool {
+        matches!(self, ContainerStatus::Running)
+    }
+
+    pub fn can_resume(&self) -> bool {
+        matches!(self, ContainerStatus::Paused)
+    }
+
+    /// Whether `bento exec` may join this container - only while its init
+    /// process is actually running.
+    pub fn can_exec(&self) -> bool {
+        matches!(self, ContainerStatus::Running)
+    }
+
+    /// Guards a transition: `Ok(())` if `allowed` holds for the current
+    /// status, else a typed [`InvalidTransition`] naming `action` and the
+    /// state it was attempted from.
+    fn guard(&self, allowed: bool, action: &'static str) -> Result<(), InvalidTransition> {
+        if allowed {
+            Ok(())
+        } else {
+            Err(InvalidTransition {
+                from: *self,
+                action,
+            })
+        }
+    }
+}
+
+/// A lifecycle command was attempted from a [`ContainerStatus`] that doesn't
+/// permit it (e.g. `start` on a `Running` container).
+#[derive(Debug, thiserror::Error)]
+#[error("cannot {action} a container in the '{from:?}' state")]
+pub struct InvalidTransition {
+    pub from: ContainerStatus,
+    pub action: &'static str,
+}
+
+/// A [`LifecycleEvent`] with the wall-clock time it was recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleEventRecord {
+    pub at: String,
+    pub event: LifecycleEvent,
+}
+
+#[cfg(test)]
+mod status_guard_tests {
+    use super::*;
+
+    #[test]
+    fn test_guard_ok_when_allowed() {
+        assert!(ContainerStatus::Created.guard(true, "start").is_ok());
+    }
+
+    #[test]
+    fn test_guard_err_names_state_and_action() {
+        let err = ContainerStatus::Running
+            .guard(false, "start")
+            .expect_err("start should be rejected while running");
+        assert_eq!(err.from, ContainerStatus::Running);
+        assert_eq!(err.action, "start");
+        assert_eq!(
+            err.to_string(),
+            "cannot start a container in the 'Running' state"
+        );
+    }
+
+    #[test]
+    fn test_lifecycle_event_status_mapping() {
+        assert_eq!(LifecycleEvent::Created.status(), ContainerStatus::Created);
+        assert_eq!(LifecycleEvent::Started.status(), ContainerStatus::Running);
+        assert_eq!(LifecycleEvent::Resumed.status(), ContainerStatus::Running);
+        assert_eq!(LifecycleEvent::Paused.status(), ContainerStatus::Paused);
+        assert_eq!(
+            LifecycleEvent::Exited { code: 0 }.status(),
+            ContainerStatus::Stopped
+        );
+        assert_eq!(
+            LifecycleEvent::Signaled { signal: 9 }.status(),
+            ContainerStatus::Stopped
+        );
+    }
+}
+
+fn unix_timestamp() -> String {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .to_string()
+}
+
+// ============================================================================
+// OCI RUNTIME STATE
+// ============================================================================
+//
+// `ContainerState` is bento's own persisted record; `OciState` is what
+// `bento state` actually prints, shaped to the OCI runtime-spec `state`
+// schema (https://github.com/opencontainers/runtime-spec/blob/main/runtime.md#state)
+// so bento's containers are inspectable by standard OCI tooling, not just
+// bento itself.
+
+/// The `ociVersion` bento's state output claims to implement. Matches the
+/// minimum `"1."` prefix [`crate::config::Config`] requires of bundles.
+const OCI_RUNTIME_SPEC_VERSION: &str = "1.0.2";
+
+/// OCI runtime-spec `state` schema rendering of a [`ContainerState`].
+#[derive(Debug, Serialize)]
+pub struct OciState {
+    #[serde(rename = "ociVersion")]
+    pub oci_version: String,
+    pub id: String,
+    pub status: ContainerStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<i32>,
+    pub bundle: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<HashMap<String, String>>,
+    /// RFC3339 rendering of [`ContainerState::created_at`].
+    pub created: String,
+    /// UID of the user that created the container.
+    pub creator: u32,
+}
+
+impl OciState {
+    /// Builds the OCI state for `state`, reconciling its persisted status
+    /// against whether the init process is actually still alive (the same
+    /// `SIGCONT` liveness probe [`ContainerInfo::from_state`] uses) so a
+    /// stale `"running"` after an unclean exit isn't reported as such.
+    fn from_container_state(state: &ContainerState) -> Self {
+        let alive = kill(Pid::from_raw(state.pid), Signal::SIGCONT).is_ok();
+        let status = reconcile_status(state.status, alive);
+        let pid = matches!(
+            status,
+            ContainerStatus::Created | ContainerStatus::Running | ContainerStatus::Paused
+        )
+        .then_some(state.pid);
+
+        Self {
+            oci_version: OCI_RUNTIME_SPEC_VERSION.to_string(),
+            id: state.id.clone(),
+            status,
+            pid,
+            bundle: state.bundle_path.clone(),
+            annotations: None,
+            created: rfc3339_from_unix_secs(&state.created_at),
+            creator: Uid::effective().as_raw(),
+        }
+    }
+}
+
+/// Reconciles a persisted [`ContainerStatus`] against whether the process
+/// it describes is actually alive right now, so a container that crashed
+/// without anyone running `bento kill` doesn't keep reporting `"running"`
+/// forever.
+fn reconcile_status(status: ContainerStatus, alive: bool) -> ContainerStatus {
+    if alive {
+        status
+    } else {
+        ContainerStatus::Stopped
+    }
+}
+
+/// Formats a Unix timestamp in seconds (as stored in
+/// [`ContainerState::created_at`]) as RFC3339 UTC, e.g.
+/// `2024-01-02T03:04:05Z`. Hand-rolled since bento has no date/time crate
+/// dependency otherwise.
+fn rfc3339_from_unix_secs(unix_secs: &str) -> String {
+    let secs: i64 = unix_secs.parse().unwrap_or(0);
+    let days = secs.div_euclid(86400);
+    let secs_of_day = secs.rem_euclid(86400);
+    let (year, month, day) = civil_from_days(days);
+    let hour = secs_of_day / 3600;
+    let minute = (secs_of_day % 3600) / 60;
+    let second = secs_of_day % 60;
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+}
+
+/// Howard Hinnant's `civil_from_days`: converts a day count since the Unix
+/// epoch into a proleptic-Gregorian `(year, month, day)`.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = (z - era * 146097) as u64; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // [0, 399]
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    let year = if m <= 2 { y + 1 } else { y };
+    (year, m, d)
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ContainerState {
     pub id: String,
     pub pid: i32,
-    pub status: String,
+    pub status: ContainerStatus,
     pub bundle_path: String,
     pub created_at: String,
-    pub start_pipe_path: Option<String>, // Store for bento start to reopen
+    pub notify_socket_path: Option<String>, // Store for bento start to connect to
+    /// Host path of the per-container log init's stdout/stderr are
+    /// redirected to, for `bento logs`/`bento attach`.
+    #[serde(default)]
+    pub log_path: Option<String>,
+    /// Exit code of the init process, once `bento kill` has reaped it.
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    /// Signal that killed the init process, if it didn't exit normally.
+    #[serde(default)]
+    pub termination_signal: Option<i32>,
+    /// Full history of [`LifecycleEvent`]s this container has gone through,
+    /// oldest first.
+    #[serde(default)]
+    pub events: Vec<LifecycleEventRecord>,
+    /// How this container's networking was set up, so `delete_container`
+    /// knows which (if any) teardown to run without re-reading the bundle.
+    #[serde(default)]
+    pub network_mode: crate::networking::NetworkMode,
+    /// The address [`crate::networking::setup_veth_network`] assigned, when
+    /// `network_mode` is [`crate::networking::NetworkMode::Veth`].
+    #[serde(default)]
+    pub network_ip: Option<String>,
+    /// Which `CgroupBackend` this container's cgroup was actually set up
+    /// with, so `cgroup_freezer_backend` dispatches to the same backend
+    /// `setup_cgroups` used instead of assuming [`crate::cgroups::CgroupDriver::Fs`].
+    #[serde(default)]
+    pub cgroup_driver: crate::cgroups::CgroupDriver,
 }
 
 impl ContainerState {
@@ -40,14 +339,46 @@ impl ContainerState {
             .as_secs()
             .to_string();
 
-        Self {
+        let mut state = Self {
             id,
             pid,
-            status: "created".to_string(),
+            status: ContainerStatus::Creating,
             bundle_path,
             created_at,
-            start_pipe_path: None, // Will be set when created
+            notify_socket_path: None, // Will be set when created
+            log_path: None,
+            exit_code: None,
+            termination_signal: None,
+            events: Vec::new(),
+            network_mode: crate::networking::NetworkMode::None,
+            network_ip: None,
+            cgroup_driver: crate::cgroups::CgroupDriver::default(),
+        };
+        state.record_event(LifecycleEvent::Created);
+        state
+    }
+
+    /// Records `event`: updates `status` (and `exit_code`/`termination_signal`
+    /// for the terminal events), and appends a timestamped entry to the
+    /// event log. Does not save - callers still own when `state.json` hits
+    /// disk.
+    pub fn record_event(&mut self, event: LifecycleEvent) {
+        self.status = event.status();
+        match event {
+            LifecycleEvent::Exited { code } => {
+                self.exit_code = Some(code);
+                self.termination_signal = None;
+            }
+            LifecycleEvent::Signaled { signal } => {
+                self.exit_code = None;
+                self.termination_signal = Some(signal);
+            }
+            _ => {}
         }
+        self.events.push(LifecycleEventRecord {
+            at: unix_timestamp(),
+            event,
+        });
     }
 }
 
@@ -58,7 +389,31 @@ fn get_state_dir() -> Result<PathBuf> {
     Ok(state_dir)
 }
 
+/// Validates that `container_id` is safe to use as a path component before
+/// it reaches any filesystem operation - `state.json`, the bundle's rootfs,
+/// the cgroup path, etc. Restricted to `[a-zA-Z0-9_.-]+` with no `..`
+/// substring, so an attacker-supplied id (e.g. from the chunk5-6 API socket)
+/// like `"../../../etc/cron.d/x"` or an absolute path can't escape the
+/// directories it's joined into. Called at every public entry point that
+/// takes a caller-supplied `container_id`, not just deep inside
+/// [`crate::fs::prepare_rootfs`] (which only validates it from inside the
+/// already-forked init process, well after `state.json` has been written).
+pub(crate) fn validate_container_id(container_id: &str) -> Result<()> {
+    let valid = !container_id.is_empty()
+        && !container_id.contains("..")
+        && container_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow!("Invalid container_id: {container_id}"))
+    }
+}
+
 fn save_container_state(container_id: &str, state: &ContainerState) -> Result<PathBuf> {
+    validate_container_id(container_id)?;
     let state_dir = get_state_dir()?;
     let state_file = state_dir.join(format!("{container_id}.json"));
     let json_content =
@@ -69,6 +424,7 @@ fn save_container_state(container_id: &str, state: &ContainerState) -> Result<Pa
 }
 
 fn load_container_state(container_id: &str) -> Result<ContainerState> {
+    validate_container_id(container_id)?;
     let state_dir = get_state_dir()?;
     let state_file = state_dir.join(format!("{container_id}.json"));
 
@@ -83,32 +439,92 @@ fn load_container_state(container_id: &str) -> Result<ContainerState> {
 }
 
 // ============================================================================
-// SYNC SIGNAL DEFINITIONS
+// TYPED CONTROL CHANNEL
 // ============================================================================
+//
+// Replaces the old single-byte `SyncSignal` protocol (which could carry
+// nothing but one opaque byte, forcing the init PID to be smuggled through
+// as raw `i32::to_le_bytes`) with a length-prefixed, serde-framed channel:
+// a 4-byte little-endian length header followed by the JSON-encoded
+// message. Crucially this lets either side send `Error(String)` instead of
+// just exiting non-zero with nothing to show for it.
+
+/// Messages the bridge process sends back to the orchestrator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum BridgeToOrchestrator {
+    Ready,
+    InitPid(i32),
+    Error(String),
+}
 
-#[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq)]
-enum SyncSignal {
-    Ready = b'R',
-    Mapped = b'M',
+/// Messages the orchestrator sends down to the bridge process.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum OrchestratorToBridge {
+    Mapped,
+    Error(String),
 }
 
-impl SyncSignal {
-    fn as_byte(&self) -> u8 {
-        *self as u8
-    }
+/// One end of a length-prefixed, serde-framed control channel over a raw
+/// pipe fd. `T` is whatever this end *receives*; sending a message of the
+/// peer's type is done via [`Channel::send_other`].
+struct Channel<T> {
+    fd: OwnedFd,
+    _marker: std::marker::PhantomData<T>,
+}
 
-    fn from_byte(byte: u8) -> Result<Self> {
-        match byte {
-            b'R' => Ok(SyncSignal::Ready),
-            b'M' => Ok(SyncSignal::Mapped),
-            _ => Err(anyhow!("Invalid sync signal byte: {}", byte as char)),
+impl<T> Channel<T>
+where
+    T: Serialize + serde::de::DeserializeOwned,
+{
+    fn new(fd: OwnedFd) -> Self {
+        Self {
+            fd,
+            _marker: std::marker::PhantomData,
         }
     }
 
-    fn as_char(&self) -> char {
-        self.as_byte() as char
+    fn recv(&self) -> Result<T> {
+        let mut header = [0u8; 4];
+        channel_read_exact(&self.fd, &mut header)?;
+        let len = u32::from_le_bytes(header) as usize;
+
+        let mut payload = vec![0u8; len];
+        channel_read_exact(&self.fd, &mut payload)?;
+
+        serde_json::from_slice(&payload).context("Failed to deserialize channel message")
+    }
+
+    fn send<U: Serialize>(&self, message: &U) -> Result<()> {
+        let payload = serde_json::to_vec(message).context("Failed to serialize channel message")?;
+        let len = u32::try_from(payload.len())
+            .map_err(|_| anyhow!("Channel message too large: {} bytes", payload.len()))?;
+
+        write(&self.fd, &len.to_le_bytes())
+            .map_err(|e| anyhow!("Failed to write channel length header: {}", e))?;
+        write(&self.fd, &payload).map_err(|e| anyhow!("Failed to write channel payload: {}", e))?;
+        Ok(())
+    }
+}
+
+fn channel_read_exact(fd: &OwnedFd, buf: &mut [u8]) -> Result<()> {
+    let mut read_total = 0;
+    while read_total < buf.len() {
+        let n = read(fd, &mut buf[read_total..])
+            .map_err(|e| anyhow!("Channel read failed: {}", e))?;
+        if n == 0 {
+            return Err(anyhow!("Channel closed before the expected data arrived"));
+        }
+        read_total += n;
     }
+    Ok(())
+}
+
+/// Sets `FD_CLOEXEC` on `fd` so it doesn't survive an `execvp` call.
+fn set_cloexec(fd: &OwnedFd) -> Result<()> {
+    use nix::fcntl::{FcntlArg, FdFlag, fcntl};
+    fcntl(fd, FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC))
+        .map_err(|e| anyhow!("Failed to set FD_CLOEXEC: {}", e))?;
+    Ok(())
 }
 
 // UPDATED: Add population_method field to Config
@@ -116,75 +532,90 @@ impl SyncSignal {
 pub struct Config {
     pub root_path: String,
     pub args: Vec<String>,
+    pub env: Vec<String>,
     pub hostname: String,
     pub rootless: bool,
     pub bundle_path: String,
     pub container_id: String,
     pub population_method: RootfsPopulationMethod, // NEW: Add this field
+    pub namespaces: Vec<crate::config::NamespaceType>,
+    /// Host file descriptors to remap into the container command before
+    /// `exec_user_command`, as `(source_fd, target_fd)` pairs. The
+    /// orchestrator creates the underlying pipes (or whatever fd the caller
+    /// wants piped through) before `create_container` forks, so these
+    /// survive down the bridge/init fork chain for init to `dup2` into
+    /// place right before exec - the same pipe_sender/pipe_receiver pattern
+    /// clone-shim uses to wire a pipe to the containerized program for
+    /// host<->container streaming.
+    pub passed_fds: Vec<(RawFd, RawFd)>,
+    /// Which networking backend (if any) [`orchestrator_handler`] should set
+    /// up once the init process's final pid is known.
+    pub network_mode: crate::networking::NetworkMode,
+    /// Host ports to forward into the container, only meaningful alongside
+    /// [`crate::networking::NetworkMode::Slirp4netns`].
+    pub port_mappings: Vec<crate::networking::PortMapping>,
+    /// `linux.seccomp` from the OCI bundle, loaded here on the host side
+    /// before `create_container` forks so it's still available to init after
+    /// [`fs::prepare_rootfs`] pivots away from the host filesystem.
+    pub seccomp: Option<crate::config2::SeccompConfig>,
+    /// Cgroup limits, from `linux.resources` in the OCI bundle unless the CLI
+    /// overrides them with explicit flags.
+    pub cgroups: crate::cgroups::CgroupsConfig,
+}
+
+impl Config {
+    /// Builds a `Config` from an OCI runtime bundle's `config.json`,
+    /// overlaying it on the defaults for anything the spec doesn't drive
+    /// (rootfs population strategy, networking, etc. stay caller-supplied;
+    /// cgroup limits come from `linux.resources` when the bundle has it).
+    pub fn from_bundle(bundle_path: &str, container_id: &str) -> Result<Self> {
+        validate_container_id(container_id)?;
+
+        let config_json = Path::new(bundle_path).join("config.json");
+        let oci = crate::config::Config::load(&config_json)
+            .with_context(|| format!("Failed to load OCI config from {}", config_json.display()))?;
+
+        let process = oci
+            .process
+            .ok_or_else(|| anyhow!("OCI config at {} has no process section", config_json.display()))?;
+
+        Ok(Self {
+            args: process.args,
+            env: process.env,
+            hostname: oci.hostname.unwrap_or_else(|| "bento-container".to_string()),
+            root_path: oci.root.path.to_string_lossy().to_string(),
+            bundle_path: bundle_path.to_string(),
+            container_id: container_id.to_string(),
+            namespaces: oci.linux.namespaces.iter().map(|ns| ns.ns_type).collect(),
+            seccomp: oci.linux.seccomp.clone(),
+            cgroups: oci
+                .linux
+                .resources
+                .as_ref()
+                .map(crate::cgroups::CgroupsConfig::from_oci)
+                .unwrap_or_default(),
+            ..Self::default()
+        })
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             root_path: "/tmp/bento-rootfs".to_string(),
-
-	    //args: vec!["/bin/sh".to_string(), "-c".to_string(), "echo '=== Bento.rs Demo: Isolation Showcase ===' && echo 'Kernel Info:' && uname -a && echo 'Hostname:' && hostname && echo 'User Info:' && whoami && id && echo 'Namespace Files:' && ls /proc/self/ns && echo 'UID Mapping:' && cat /proc/self/uid_map && echo 'Process Tree:' && ps aux && echo 'Mount Points:' && cat /proc/mounts && echo 'Environment:' && env && echo '=== End Demo: Functional Container Achieved! ==='".to_string()],
-
-	/*args: vec!["/bin/sh".to_string(), "-c".to_string(), 
-    "echo '=== Bento.rs Demo: Isolation Showcase ===' && \
-    echo 'Kernel Info:' && uname -a && \
-    echo 'Hostname:' && hostname && \
-    echo 'User Info:' && whoami && id && \
-    echo 'Namespace Files:' && ls /proc/self/ns && \
-    echo 'UID Mapping:' && cat /proc/self/uid_map && \
-    echo 'Process Tree:' && ps aux && \
-    echo 'Mount Points:' && cat /proc/mounts && \
-    echo 'Environment:' && env && \
-    echo '=== End Demo: Functional Container Achieved! ==='".to_string()],
-*/
-
-args: vec!["/bin/sh".to_string(), "-c".to_string(), 
-    "echo '=== Bento.rs Demo: Isolation Showcase ===' && \
-    echo -n 'Kernel Info: ' && uname -a && \
-    echo -n 'Hostname: ' && hostname && \
-    echo -n 'User Info: ' && whoami && echo -n 'ID: ' && id && \
-    echo -n 'Namespace Files: ' && ls /proc/self/ns && \
-    echo -n 'UID Mapping: ' && cat /proc/self/uid_map && \
-    echo -n 'Process Tree: ' && ps aux && \
-    echo -n 'Mount Points: ' && cat /proc/mounts && \
-    echo '=== End Demo: Functional Container Achieved! ==='".to_string()],
-
-
-            //args: vec!["/bin/sh".to_string(), "-c".to_string(), "cat /proc/meminfo | head -5 && echo 'System info accessible'".to_string()],
-            //args: vec!["/bin/sh".to_string(), "-c".to_string(), "env | sort && echo 'PATH:' $PATH".to_string()],
-            //args: vec!["/bin/sh".to_string(), "-c".to_string(), "ls -la /bin | head -10 && echo 'Filesystem test complete'".to_string()],
-            //args: vec![ "/bin/sh".to_string(), "-c".to_string(), "ps aux && echo 'Process count:' $(ps aux | wc -l)".to_string(), ],
-            //args: vec!["/bin/sh".to_string(), "-c".to_string(), "uname -a && hostname && echo 'Working directory:' $(pwd)".to_string()],
-            /*
-            args: vec!["/bin/sh".to_string(), "-c".to_string(),
-                       "echo '=== CONTAINER SYSTEM REPORT ===' && echo 'User Information:' && whoami && id && echo 'System Information:' && hostname && uname -a && echo 'Available Commands:' && ls /bin | head -10 && echo '=== END REPORT ==='".to_string()],
-            //didnt work at all
-             */
-
-            /* args: vec!["/bin/sh".to_string(), "-c".to_string(),
-                       "echo '=== FILESYSTEM ANALYSIS ===' && echo 'Root directory:' && ls -la / && echo 'Proc filesystem:' && ls /proc | head -5 && echo 'Device filesystem:' && ls /dev | head -5 && echo 'Mount points:' && mount && echo '=== END ANALYSIS ==='".to_string()],
-            // worked tho
-            */
-
-
-                    //args: vec!["/bin/sh".to_string()],//executed and killed terminal
-                        //args: vec![ "/bin/whoami".to_string() ], //worked but gave wrong value
-                        //args: vec!["/bin/sh".to_string(),"-i".to_string()],
-                    /*args: vec![
-                            "/bin/sh".to_string(),
-                            "-c".to_string(),
-                            "/bin/ls /bin; /bin/echo 'PATH test'; echo $PATH".to_string(),
-                        ],*/
+            args: vec!["/bin/sh".to_string()],
+            env: Vec::new(),
             hostname: "bento-container".to_string(),
             rootless: true,
             bundle_path: ".".to_string(),
             container_id: "default".to_string(),
             population_method: RootfsPopulationMethod::BusyBox, // NEW: Default to reliable method
+            namespaces: Vec::new(),
+            passed_fds: Vec::new(),
+            network_mode: crate::networking::NetworkMode::None,
+            port_mappings: Vec::new(),
+            seccomp: None,
+            cgroups: crate::cgroups::CgroupsConfig::default(),
         }
     }
 }
@@ -201,19 +632,26 @@ impl ContainerPipes {
             pipe().map_err(|e| anyhow!("Failed to create orchestrator->bridge pipe: {}", e))?;
         let bridge_to_orchestrator =
             pipe().map_err(|e| anyhow!("Failed to create bridge->orchestrator pipe: {}", e))?;
-        let start_pipe = pipe().map_err(|e| anyhow!("Failed to create start pipe: {}", e))?;
 
-        println!("[Sync] All pipes created (sync + start)");
+        // These fds are only meant for the orchestrator/bridge handshake;
+        // mark them CLOEXEC so a stray copy inherited across fork() can't
+        // survive into the init process's eventual `execvp` of the user
+        // command.
+        set_cloexec(&orchestrator_to_bridge.0)?;
+        set_cloexec(&orchestrator_to_bridge.1)?;
+        set_cloexec(&bridge_to_orchestrator.0)?;
+        set_cloexec(&bridge_to_orchestrator.1)?;
+
+        println!("[Sync] Sync pipes created");
 
         let orchestrator_pipes = OrchestratorPipes {
-            read_fd: bridge_to_orchestrator.0,
-            write_fd: orchestrator_to_bridge.1,
+            rx: Channel::new(bridge_to_orchestrator.0),
+            tx: Channel::new(orchestrator_to_bridge.1),
         };
 
         let bridge_pipes = BridgePipes {
-            read_fd: orchestrator_to_bridge.0,
-            write_fd: bridge_to_orchestrator.1,
-            start_read_fd: start_pipe.0, // Pass read end through bridge to init
+            rx: Channel::new(orchestrator_to_bridge.0),
+            tx: Channel::new(bridge_to_orchestrator.1),
         };
 
         Ok((orchestrator_pipes, bridge_pipes))
@@ -221,40 +659,13 @@ impl ContainerPipes {
 }
 
 struct OrchestratorPipes {
-    read_fd: OwnedFd,
-    write_fd: OwnedFd,
-    //start_write_fd: OwnedFd, // For writing to unblock init
+    rx: Channel<BridgeToOrchestrator>,
+    tx: Channel<OrchestratorToBridge>,
 }
 
 struct BridgePipes {
-    read_fd: OwnedFd,
-    write_fd: OwnedFd,
-    start_read_fd: OwnedFd, // Pass through to init
-}
-
-// Common pipe operations (reduces repetition)
-fn pipe_signal(fd: &OwnedFd, signal: SyncSignal, context: &str) -> Result<()> {
-    write(fd, &[signal.as_byte()])
-        .map_err(|e| anyhow!("Failed to send {} signal: {}", context, e))?;
-    println!("[{}] Sent '{}' signal", context, signal.as_char());
-    Ok(())
-}
-
-fn pipe_wait(fd: &OwnedFd, expected: SyncSignal, context: &str) -> Result<()> {
-    let mut buf = [0u8; 1];
-    read(fd, &mut buf).map_err(|e| anyhow!("Failed to receive {} signal: {}", context, e))?;
-    let received = SyncSignal::from_byte(buf[0])?;
-    if received != expected {
-        return Err(anyhow!(
-            "Expected '{}', got '{}' in {}",
-            expected.as_char(),
-            received.as_char(),
-            context
-        ));
-    }
-
-    println!("[{}] Received '{}' signal", context, received.as_char());
-    Ok(())
+    rx: Channel<OrchestratorToBridge>,
+    tx: Channel<BridgeToOrchestrator>,
 }
 
 // ============================================================================
@@ -262,7 +673,7 @@ fn pipe_wait(fd: &OwnedFd, expected: SyncSignal, context: &str) -> Result<()> {
 // ============================================================================
 
 pub fn create_container(config: &Config) -> Result<()> {
-    cleanup_named_pipes(&config.container_id).context("Failed to cleanup stale named pipes")?;
+    cleanup_notify_socket(&config.container_id).context("Failed to cleanup stale notify socket")?;
 
     let (orchestrator_pipes, bridge_pipes) = ContainerPipes::create()?;
     println!("Bento.rs Rootless Container Runtime");
@@ -284,53 +695,100 @@ pub fn create_container(config: &Config) -> Result<()> {
 // ORCHESTRATOR PROCESS LOGIC (Container Creation Coordinator)
 // ============================================================================
 
+/// Sets up `config.network_mode`'s networking against the container's final
+/// pid, once it's known, and records the assigned address onto `state`.
+/// Requires a `net` namespace to actually exist; otherwise there's nothing
+/// for either backend to attach to, so this just warns and leaves
+/// `state.network_ip` unset rather than failing container creation over it.
+fn setup_container_network(config: &Config, pid: i32, state: &mut ContainerState) {
+    if matches!(config.network_mode, crate::networking::NetworkMode::None) {
+        return;
+    }
+
+    let has_net_namespace = config
+        .namespaces
+        .iter()
+        .any(|ns| matches!(ns, crate::config::NamespaceType::Net));
+    if !has_net_namespace {
+        println!(
+            "[Orchestrator] Warning: network_mode {:?} requested without a net namespace - skipping",
+            config.network_mode
+        );
+        return;
+    }
+
+    let result = match config.network_mode {
+        crate::networking::NetworkMode::Veth => {
+            crate::networking::setup_veth_network(&config.container_id, Pid::from_raw(pid))
+                .map(|ip| Some(ip.to_string()))
+        }
+        crate::networking::NetworkMode::Slirp4netns => crate::networking::setup_slirp_network(
+            &config.container_id,
+            Pid::from_raw(pid),
+            &config.port_mappings,
+        )
+        .map(|()| None),
+        crate::networking::NetworkMode::None => Ok(None),
+    };
+
+    match result {
+        Ok(ip) => state.network_ip = ip,
+        Err(e) => println!(
+            "[Orchestrator] Warning: network setup failed for '{}': {e}",
+            config.container_id
+        ),
+    }
+}
+
+/// Applies `config.cgroups` (from `linux.resources`, or CLI overrides) to the
+/// container's final pid, once it's known. Soft-fails with a warning rather
+/// than aborting creation, matching [`setup_container_network`] - a host
+/// without cgroup v2/v1 support shouldn't keep an otherwise-working
+/// container from starting.
+fn setup_container_cgroups(config: &Config, pid: i32) {
+    if let Err(e) = crate::cgroups::setup_cgroups(
+        &config.cgroups,
+        &config.container_id,
+        Pid::from_raw(pid),
+        Path::new("/sys/fs/cgroup"),
+    ) {
+        println!(
+            "[Orchestrator] Warning: cgroup setup failed for '{}': {e}",
+            config.container_id
+        );
+    }
+}
+
 fn orchestrator_handler(bridge_pid: Pid, pipes: OrchestratorPipes, config: &Config) -> Result<()> {
     println!("[Orchestrator] Bridge spawned with PID: {bridge_pid}");
 
     // Wait for bridge namespace ready signal
     println!("[Orchestrator] Waiting for bridge namespace ready signal...");
-    pipe_wait(&pipes.read_fd, SyncSignal::Ready, "Orchestrator")?;
+    match pipes.rx.recv()? {
+        BridgeToOrchestrator::Ready => {}
+        BridgeToOrchestrator::Error(e) => return Err(anyhow!("Bridge reported error: {}", e)),
+        other => return Err(anyhow!("Expected Ready from bridge, got {:?}", other)),
+    }
 
     // Perform UID/GID mapping
     map_user_namespace_rootless(bridge_pid)?;
     println!("[Orchestrator] UID/GID mapping completed successfully");
 
     // Signal mapping complete
-    pipe_signal(&pipes.write_fd, SyncSignal::Mapped, "Orchestrator")?;
+    pipes.tx.send(&OrchestratorToBridge::Mapped)?;
+    println!("[Orchestrator] Sent Mapped message");
 
     // Receive init process PID
     println!("[Orchestrator] Waiting for final container PID...");
-    let mut pid_buf = [0u8; 4];
-    read(&pipes.read_fd, &mut pid_buf).map_err(|e| anyhow!("Failed to receive init PID: {}", e))?;
-    let final_container_pid = i32::from_le_bytes(pid_buf);
+    let final_container_pid = match pipes.rx.recv()? {
+        BridgeToOrchestrator::InitPid(pid) => pid,
+        BridgeToOrchestrator::Error(e) => return Err(anyhow!("Bridge reported error: {}", e)),
+        other => return Err(anyhow!("Expected InitPid from bridge, got {:?}", other)),
+    };
     println!("[Orchestrator] Final container PID: {final_container_pid}");
 
-    cleanup_named_pipes(&config.container_id).context("Failed to cleanup pipes before creation")?;
-
-    // State management
-    let home = std::env::var("HOME").context("HOME environment variable not set")?;
-    let container_rootfs = format!("{}/.local/share/bento/{}/rootfs", home, config.container_id);
-    let start_pipe_path = format!(
-        "{}/tmp/bento-start-{}",
-        container_rootfs, config.container_id
-    );
-
-    // Ensure tmp directory exists in container rootfs
-    std_fs::create_dir_all(format!("{container_rootfs}/tmp"))?;
-
-    let _ = std::fs::remove_file(&start_pipe_path);
-
-    // Create FIFO in container's filesystem
-    match mkfifo(start_pipe_path.as_str(), Mode::S_IRUSR | Mode::S_IWUSR) {
-        Ok(_) => println!("[Orchestrator] Created start pipe: {}", start_pipe_path),
-        Err(e) => {
-            eprintln!(
-                "[Orchestrator] Failed to create start pipe {}: {}",
-                start_pipe_path, e
-            );
-            // Continue anyway - the error will be caught later
-        }
-    }
+    cleanup_notify_socket(&config.container_id)
+        .context("Failed to cleanup notify socket before creation")?;
 
     // Create and save state.json
     let mut container_state = ContainerState::new(
@@ -338,9 +796,24 @@ fn orchestrator_handler(bridge_pid: Pid, pipes: OrchestratorPipes, config: &Conf
         final_container_pid,
         config.bundle_path.clone(),
     );
+    container_state.cgroup_driver = config.cgroups.driver;
+
+    // Store the container-relative path (what init will see after pivot_root).
+    // Init binds this socket itself, once it's inside the container's
+    // namespaces and rootfs, so there's nothing for the orchestrator to
+    // create here - just the path `bento start` will later connect to.
+    container_state.notify_socket_path = Some(notify_socket_path(&config.container_id));
 
-    // Store the container-relative path (what init will see after pivot_root)
-    container_state.start_pipe_path = Some(format!("/tmp/bento-start-{}", config.container_id));
+    // Host path init redirects its stdout/stderr onto before `prepare_rootfs`
+    // pivots away from the host filesystem.
+    container_state.log_path = container_log_path(&config.container_id)
+        .ok()
+        .map(|p| p.to_string_lossy().into_owned());
+
+    setup_container_cgroups(config, final_container_pid);
+
+    container_state.network_mode = config.network_mode.clone();
+    setup_container_network(config, final_container_pid, &mut container_state);
 
     save_container_state(&config.container_id, &container_state)
         .context("Failed to save container state")?;
@@ -440,59 +913,61 @@ fn bridge_handler(config: &Config, pipes: BridgePipes) -> isize {
     // Phase 1: User namespace setup
     if let Err(e) = setup_user_namespace(&pipes) {
         eprintln!("[Bridge] User namespace setup failed: {e}");
+        let _ = pipes.tx.send(&BridgeToOrchestrator::Error(e.to_string()));
         return 1;
     }
 
     // Phase 2: Wait for UID/GID mapping
     if let Err(e) = wait_for_mapping(&pipes) {
         eprintln!("[Bridge] Mapping synchronization failed: {e}");
+        let _ = pipes.tx.send(&BridgeToOrchestrator::Error(e.to_string()));
         return 1;
     }
 
     // Phase 3: Create remaining namespaces
-    if let Err(e) = create_remaining_namespaces() {
+    if let Err(e) = create_remaining_namespaces(config) {
         eprintln!("[Bridge] Remaining namespaces creation failed: {e}");
+        let _ = pipes.tx.send(&BridgeToOrchestrator::Error(e.to_string()));
         return 1;
     }
 
     // Phase 4: Create init process and communicate PID
-    create_init_with_start_pipe(config, &pipes)
+    create_init_process(config, &pipes)
 }
 
 // Helper functions for bridge phases
 fn setup_user_namespace(pipes: &BridgePipes) -> Result<()> {
     unshare_user_namespace()?;
     disable_setgroups_for_child()?;
-    pipe_signal(&pipes.write_fd, SyncSignal::Ready, "Bridge")?;
+    pipes.tx.send(&BridgeToOrchestrator::Ready)?;
+    println!("[Bridge] Sent Ready message");
     Ok(())
 }
 
 fn wait_for_mapping(pipes: &BridgePipes) -> Result<()> {
-    println!("[Bridge] Waiting for mapping complete signal...");
-    pipe_wait(&pipes.read_fd, SyncSignal::Mapped, "Bridge")?;
-    Ok(())
+    println!("[Bridge] Waiting for mapping complete message...");
+    match pipes.rx.recv()? {
+        OrchestratorToBridge::Mapped => Ok(()),
+        OrchestratorToBridge::Error(e) => Err(anyhow!("Orchestrator reported error: {}", e)),
+    }
 }
 
-fn create_remaining_namespaces() -> Result<()> {
-    unshare_remaining_namespaces()
+fn create_remaining_namespaces(config: &Config) -> Result<()> {
+    unshare_remaining_namespaces(&config.namespaces)
         .map_err(|e| anyhow!("Failed to create remaining namespaces: {}", e))
 }
 
-fn create_init_with_start_pipe(config: &Config, pipes: &BridgePipes) -> isize {
+fn create_init_process(config: &Config, pipes: &BridgePipes) -> isize {
     println!("[Bridge] Creating init process...");
 
-    // Get the raw FD before fork
-    let start_pipe_fd = pipes.start_read_fd.as_raw_fd();
-
     match unsafe { fork() } {
         Ok(ForkResult::Parent {
             child: init_process,
         }) => {
-            // Parent (bridge) - properly drop the read end
-            let _ = &pipes.start_read_fd; // Drop reference to allow cleanup
-
-            let pid_bytes = init_process.as_raw().to_le_bytes();
-            if let Err(e) = write(&pipes.write_fd, &pid_bytes) {
+            if let Err(e) = pipes
+                .tx
+                .send(&BridgeToOrchestrator::InitPid(init_process.as_raw()))
+            {
                 eprintln!("[Bridge] Failed to send init PID: {e}");
                 return 1;
             }
@@ -500,10 +975,7 @@ fn create_init_with_start_pipe(config: &Config, pipes: &BridgePipes) -> isize {
             println!("[Bridge] Mission complete - exiting");
             0
         }
-        Ok(ForkResult::Child) => {
-            // Child (init) - keep start_pipe_fd for blocking
-            init_handler_with_pause(config, start_pipe_fd)
-        }
+        Ok(ForkResult::Child) => init_handler_with_pause(config),
         Err(e) => {
             eprintln!("[Bridge] Failed to fork init process: {e}");
             1
@@ -511,15 +983,120 @@ fn create_init_with_start_pipe(config: &Config, pipes: &BridgePipes) -> isize {
     }
 }
 
+// ============================================================================
+// NOTIFY SOCKET (Init <-> `bento start` handshake)
+// ============================================================================
+//
+// A `SOCK_SEQPACKET` Unix domain socket that init binds itself, after it has
+// entered the user namespace (and pivoted into the container rootfs), so the
+// socket file ends up owned by the mapped container root instead of leaking
+// host ownership. SEQPACKET gives atomic, whole-message `recv`s - no
+// short-read handling like a FIFO or stream socket needs - and a failed
+// `connect`/`recv` tells `bento start` immediately that init died, standing
+// in for what used to be a separate `SIGCONT` liveness probe.
+
+const START_REQUEST: &[u8] = b"start";
+const START_ACK: &[u8] = b"ack";
+
+/// Container-relative path (as init, post-pivot_root, will see it) of the
+/// notify socket `bento start` connects to.
+fn notify_socket_path(container_id: &str) -> String {
+    format!("/tmp/bento-notify-{container_id}.sock")
+}
+
+/// Binds the notify socket. Must be called by init itself, after it has
+/// entered the container's namespaces, so the listening socket is owned by
+/// the container's (mapped) root rather than the host user.
+fn bind_notify_socket(socket_path: &str) -> Result<OwnedFd> {
+    let _ = std_fs::remove_file(socket_path);
+
+    let fd = socket(AddressFamily::Unix, SockType::SeqPacket, SockFlag::empty(), None)
+        .map_err(|e| anyhow!("Failed to create notify socket: {e}"))?;
+    let addr = UnixAddr::new(socket_path)
+        .map_err(|e| anyhow!("Invalid notify socket path {socket_path}: {e}"))?;
+    bind(fd.as_raw_fd(), &addr)
+        .with_context(|| format!("Failed to bind notify socket: {socket_path}"))?;
+    listen(&fd, Backlog::new(1).expect("1 is a valid listen backlog"))
+        .with_context(|| format!("Failed to listen on notify socket: {socket_path}"))?;
+
+    Ok(fd)
+}
+
+/// Blocks until `bento start` connects and sends the start request, handing
+/// back the still-open connection so the caller can ack or report an error
+/// before closing it.
+fn accept_start_request(listener: &OwnedFd) -> Result<OwnedFd> {
+    let conn = accept(listener.as_raw_fd())
+        .map(|fd| unsafe { OwnedFd::from_raw_fd(fd) })
+        .context("Failed to accept notify socket connection")?;
+
+    let mut buffer = [0u8; 5]; // One SEQPACKET recv == one whole "start" message
+    let n = recv(conn.as_raw_fd(), &mut buffer, MsgFlags::empty())
+        .context("Failed to read start request from notify socket")?;
+
+    if &buffer[..n] != START_REQUEST {
+        return Err(anyhow!(
+            "Invalid start request received: {:?}",
+            String::from_utf8_lossy(&buffer[..n])
+        ));
+    }
+
+    Ok(conn)
+}
+
 // ============================================================================
 // INIT PROCESS LOGIC (Container Init - PID 1)
 // ============================================================================
 
-fn init_handler_with_pause(config: &Config, _start_pipe_fd: i32) -> isize {
+/// Host path of the per-container log captured stdout/stderr are written to.
+/// Computed the same way by the orchestrator (to record it in
+/// `ContainerState`) and by init itself (to open it), since init resolves it
+/// before [`fs::prepare_rootfs`] pivots away from the host filesystem.
+fn container_log_path(container_id: &str) -> Result<PathBuf> {
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    Ok(PathBuf::from(format!(
+        "{home}/.local/share/bento/{container_id}/container.log"
+    )))
+}
+
+/// Redirects stdout/stderr onto the per-container log file so a backgrounded
+/// container's output isn't lost once it's detached from bento's terminal.
+/// Must run before [`fs::prepare_rootfs`] pivots the mount namespace, since
+/// the log lives at a host path.
+fn redirect_stdio_to_log(container_id: &str) -> Result<()> {
+    let log_path = container_log_path(container_id)?;
+    if let Some(parent) = log_path.parent() {
+        std_fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create log directory {}", parent.display()))?;
+    }
+
+    let log_file = std_fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("Failed to open container log {}", log_path.display()))?;
+
+    nix::unistd::dup2(log_file.as_raw_fd(), 1)
+        .context("Failed to redirect stdout to container log")?;
+    nix::unistd::dup2(log_file.as_raw_fd(), 2)
+        .context("Failed to redirect stderr to container log")?;
+
+    println!("[Init] Container output captured to {}", log_path.display());
+    Ok(())
+}
+
+fn init_handler_with_pause(config: &Config) -> isize {
     println!("[Init] I am PID 1 in container: {}", getpid());
     println!("[Init] Container ID: {}", config.container_id);
     //println!("[Init] Command to execute: {:?}", config.args);
 
+    // Phase 0: Capture stdout/stderr to the per-container log before the
+    // host filesystem becomes unreachable.
+    if let Err(e) = redirect_stdio_to_log(&config.container_id) {
+        eprintln!("[Init] Failed to set up container log: {e}");
+        return 1;
+    }
+
     // Phase 1: Filesystem preparation with validation
     match fs::prepare_rootfs(&config.container_id, config) {
         Ok(_) => {
@@ -561,154 +1138,140 @@ fn init_handler_with_pause(config: &Config, _start_pipe_fd: i32) -> isize {
     }
 
     // Phase 3: Environment setup
-    if let Err(e) = setup_container_environment() {
+    if let Err(e) = setup_container_environment(&config.env) {
         eprintln!("[Init] Failed to setup environment: {}", e);
         return 1;
     }
 
-    // Phase 4: Enter PAUSE state
-    let start_pipe_path = format!("/tmp/bento-start-{}", config.container_id);
+    // Phase 4: Bind the notify socket and enter PAUSE state until `bento
+    // start` connects.
+    let socket_path = notify_socket_path(&config.container_id);
     println!("[Init] Container setup complete - entering PAUSE state");
-    println!("[Init] Waiting for signal at: {}", start_pipe_path);
-    println!(
-        "[Init] Current working directory: {:?}",
-        std::env::current_dir()
-    );
-    println!("[Init] Current PATH: {:?}", std::env::var("PATH"));
+    println!("[Init] Listening for start request on: {}", socket_path);
 
-    // Read start signal with proper error handling
-    match read_start_signal(&start_pipe_path) {
-        Ok(_) => {
-            println!("[Init] Start signal received successfully");
-        }
+    let listener = match bind_notify_socket(&socket_path) {
+        Ok(listener) => listener,
         Err(e) => {
-            eprintln!("[Init] Failed to read start signal: {}", e);
+            eprintln!("[Init] Failed to bind notify socket: {e}");
             return 1;
         }
-    }
-
-    // Phase 5: Execute user command with extensive debugging
-    println!("[Init] About to execute command: {:?}", config.args);
-    println!(
-        "[Init] Current working directory before exec: {:?}",
-        std::env::current_dir()
-    );
-    println!("[Init] Environment PATH: {:?}", std::env::var("PATH"));
+    };
 
-    // Test command one more time before exec
-    if !config.args.is_empty() {
-        let cmd = &config.args[0];
-        if Path::new(cmd).exists() {
-            println!("[Init] ✓ Final validation: Command {} exists", cmd);
-        } else {
-            eprintln!("[Init] ✗ CRITICAL: Command {} missing at exec time!", cmd);
+    let conn = match accept_start_request(&listener) {
+        Ok(conn) => {
+            println!("[Init] Start request received");
+            conn
+        }
+        Err(e) => {
+            eprintln!("[Init] Failed to read start request: {e}");
             return 1;
         }
-    }
-
-    exec_user_command(config)
-}
-
-// Enhanced start signal reading with complete I/O handling
-fn read_start_signal(pipe_path: &str) -> Result<()> {
-    use std::io::Read;
-
-    println!("[Init] Opening start pipe: {}", pipe_path);
-
-    let mut pipe = std::fs::OpenOptions::new()
-        .read(true)
-        .open(pipe_path)
-        .with_context(|| format!("Failed to open start pipe: {}", pipe_path))?;
-
-    let mut buffer = [0u8; 5]; // Expect exactly "start" (5 bytes)
-
-    // Use read_exact for atomic, complete reads
-    pipe.read_exact(&mut buffer)
-        .context("Failed to read complete start signal from pipe")?;
-
-    // Verify signal content
-    if &buffer == b"start" {
-        println!("[Init] Received valid start signal");
-        Ok(())
-    } else {
-        Err(anyhow!(
-            "Invalid start signal received: {:?}",
-            String::from_utf8_lossy(&buffer)
-        ))
-    }
-}
-
-/*
-fn init_handler_with_pause(config: &Config, _start_pipe_fd: i32) -> isize {
-    println!("[Init] I am PID 1 in container: {}", getpid());
+    };
 
-    if let Err(e) = debug_namespace_info() {
-        eprintln!("[Init] Failed to debug namespace info: {e}");
+    // Phase 5: Validate the command is actually runnable, acking (or
+    // erroring) the notify socket before either execing or bailing out.
+    if config.args.is_empty() {
+        let _ = send(conn.as_raw_fd(), b"error: no command specified", MsgFlags::empty());
+        eprintln!("[Init] No command specified");
+        return 1;
     }
 
-    // Phase 1: Filesystem preparation - FIXED: Pass config parameter
-    match fs::prepare_rootfs(&config.container_id, config) {
-        Ok(_) => println!("[Init] Filesystem prepared successfully"),
-        Err(e) => {
-            eprintln!("[Init] Filesystem preparation failed: {e}");
-            return 1;
-        }
+    let cmd = &config.args[0];
+    if !Path::new(cmd).exists() {
+        let msg = format!("error: command not found: {cmd}");
+        let _ = send(conn.as_raw_fd(), msg.as_bytes(), MsgFlags::empty());
+        eprintln!("[Init] Command {cmd} missing at exec time!");
+        return 1;
     }
 
-    // Phase 2: Set hostname
-    if let Err(e) = set_container_hostname(&config.hostname) {
-        eprintln!("[Init] Failed to set hostname: {e}");
+    if let Err(e) = send(conn.as_raw_fd(), START_ACK, MsgFlags::empty()) {
+        eprintln!("[Init] Failed to ack start request: {e}");
         return 1;
     }
+    drop(conn);
 
-    // Phase 3: Environment setup
-    if let Err(e) = setup_container_environment() {
-        eprintln!("[Init] Failed to setup environment: {e}");
+    // Remap any host fds the caller wants piped into the container command
+    // (e.g. a pipe wired to stdin/stdout for host<->container streaming)
+    // before sweeping everything else, so the targets survive the sweep.
+    if let Err(e) = remap_passed_fds(&config.passed_fds) {
+        eprintln!("[Init] Failed to remap passed fds: {e}");
         return 1;
     }
 
-    // Phase 4: Enter PAUSE state - BLOCK HERE until bento start
-    let start_pipe_path = format!("/tmp/bento-start-{}", config.container_id);
-    println!("[Init] Container setup complete - entering PAUSE state");
-    println!("[Init] Waiting for signal at: {start_pipe_path}");
+    // Close anything left over from the orchestrator/bridge fork chain (or
+    // opened while preparing the rootfs) so it doesn't leak into the
+    // container as a stray host fd.
+    let keep: Vec<RawFd> = config.passed_fds.iter().map(|&(_, target)| target).collect();
+    close_inherited_fds(&keep);
 
-    // Open named pipe for reading (this blocks until writer opens)
-    match std_fs::OpenOptions::new().read(true).open(&start_pipe_path) {
-        Ok(_pipe) => {
-            let _buffer = [0u8; 1];
+    println!("[Init] About to execute command: {:?}", config.args);
+    run_under_seccomp(config.seccomp.clone(), || exec_user_command(config))
+}
 
-            match read_start_signal(&start_pipe_path) {
-            Ok(()) => {
-                println!("[Init] Start signal processing complete");
-            }
-                Err(e) => {
-                eprintln!("[Init] Failed to process start signal: {e}");
-                return 1;
-                }
-        }
+/// `dup2`s each `(source_fd, target_fd)` pair from `config.passed_fds` into
+/// place. The source fds were opened by the orchestrator before
+/// `create_container` forked and ride down the bridge/init fork chain
+/// uninvited (they aren't CLOEXEC), so by the time init runs they're still
+/// valid descriptors in its own fd table - just not yet at the numbers the
+/// container command expects (e.g. 0/1/2).
+fn remap_passed_fds(passed_fds: &[(RawFd, RawFd)]) -> Result<()> {
+    for &(source, target) in passed_fds {
+        nix::unistd::dup2(source, target)
+            .map_err(|e| anyhow!("Failed to dup2 passed fd {source} -> {target}: {e}"))?;
     }
+    Ok(())
+}
+
+/// Closes every open file descriptor above stderr by walking
+/// `/proc/self/fd`, rather than guessing an upper bound. Run this
+/// immediately before the final `exec_user_command` so CLOEXEC misses (or
+/// fds opened for reasons other than the control channels, e.g. rootfs
+/// preparation) can't leak into the container. `keep` exempts the targets
+/// [`remap_passed_fds`] just dup'd into place (and their sources, if also
+/// listed) from the sweep.
+fn close_inherited_fds(keep: &[RawFd]) {
+    let fds: Vec<i32> = match std_fs::read_dir("/proc/self/fd") {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().to_str().and_then(|s| s.parse::<i32>().ok()))
+            .filter(|fd| *fd > 2 && !keep.contains(fd))
+            .collect(),
         Err(e) => {
-            eprintln!("[Init] Failed to open start pipe: {e}");
-            return 1;
+            eprintln!("[Init] Failed to list /proc/self/fd, skipping fd cleanup: {e}");
+            return;
         }
-    }
+    };
 
-    // Phase 5: Execute user command
-    println!("[Init] Executing user command: {:?}", config.args);
-    exec_user_command(config)
+    for fd in fds {
+        let _ = nix::unistd::close(fd);
+    }
 }
 
-*/
 
 // NEW: Environment setup function
-fn setup_container_environment() -> Result<()> {
-    unsafe {
-        std::env::set_var("PATH", "/bin:/usr/bin");
-        std::env::set_var("HOME", "/");
-        std::env::set_var("USER", "root");
-        std::env::set_var("SHELL", "/bin/sh");
-        std::env::set_var("TERM", "xterm");
+///
+/// Applies `process.env` from the OCI bundle (`"KEY=VALUE"` entries) if any
+/// were given, falling back to bento's historical demo defaults otherwise so
+/// bare-bones configs still get a usable shell environment.
+fn setup_container_environment(env: &[String]) -> Result<()> {
+    if env.is_empty() {
+        unsafe {
+            std::env::set_var("PATH", "/bin:/usr/bin");
+            std::env::set_var("HOME", "/");
+            std::env::set_var("USER", "root");
+            std::env::set_var("SHELL", "/bin/sh");
+            std::env::set_var("TERM", "xterm");
+        }
+    } else {
+        for entry in env {
+            if let Some((key, value)) = entry.split_once('=') {
+                unsafe {
+                    std::env::set_var(key, value);
+                }
+            }
+        }
     }
+
     println!("[Container] Environment configured");
     Ok(())
 }
@@ -727,61 +1290,30 @@ fn set_container_hostname(hostname: &str) -> Result<()> {
         }
     }
 }
-/*
-fn debug_namespace_info() -> Result<()> {
-    use std::fs;
-
-    println!("[Debug] Current process namespace information:");
-
-    // Check PID namespace
-    let pid_ns = fs::read_link("/proc/self/ns/pid").context("Failed to read PID namespace")?;
-    println!("[Debug] PID namespace: {pid_ns:?}");
-
-    // Check mount namespace
-    let mnt_ns = fs::read_link("/proc/self/ns/mnt").context("Failed to read mount namespace")?;
-    println!("[Debug] Mount namespace: {mnt_ns:?}");
-
-    // Check user namespace
-    let user_ns = fs::read_link("/proc/self/ns/user").context("Failed to read user namespace")?;
-    println!("[Debug] User namespace: {user_ns:?}");
-
-    // Check UTS namespace (hostname)
-    let uts_ns = fs::read_link("/proc/self/ns/uts").context("Failed to read UTS namespace")?;
-    println!("[Debug] UTS namespace: {uts_ns:?}");
-
-    // Check current PID as seen by process
-    println!(
-        "[Debug] Current PID (should be 1 in container): {}",
-        nix::unistd::getpid()
-    );
-
-    // Check parent PID
-    println!("[Debug] Parent PID: {}", nix::unistd::getppid());
-
-    Ok(())
-}
-*/
 fn exec_user_command(config: &Config) -> isize {
+    exec_args(&config.args)
+}
+
+/// Replaces the current process image with `args[0]`, searched via `PATH`
+/// (`execvp`), passing the rest as its argv. Shared by the PID 1 init path
+/// ([`exec_user_command`]) and [`exec_container`]'s tenant path.
+fn exec_args(args: &[String]) -> isize {
     use nix::unistd::execvp;
     use std::ffi::CString;
 
-    // Convert args to CString
-    let c_args: Result<Vec<CString>, _> = config
-        .args
-        .iter()
-        .map(|arg| CString::new(arg.as_str()))
-        .collect();
+    let c_args: Result<Vec<CString>, _> =
+        args.iter().map(|arg| CString::new(arg.as_str())).collect();
 
     let c_args = match c_args {
         Ok(args) => args,
         Err(e) => {
-            eprintln!("[Init] Failed to convert args to CString: {e}");
+            eprintln!("[Exec] Failed to convert args to CString: {e}");
             return 1;
         }
     };
 
     if c_args.is_empty() {
-        eprintln!("[Init] No command specified");
+        eprintln!("[Exec] No command specified");
         return 1;
     }
 
@@ -792,115 +1324,722 @@ fn exec_user_command(config: &Config) -> isize {
             unreachable!("execvp returned successfully");
         }
         Err(e) => {
-            eprintln!("[Init] execvp failed: {e}");
+            eprintln!("[Exec] execvp failed: {e}");
             1
         }
     }
 }
 
-pub fn start_container(container_id: &str) -> Result<()> {
-    // Load container state
-    let mut state = load_container_state(container_id)
-        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
-
+/// Prints `container_id`'s OCI runtime-spec state as JSON to stdout (what
+/// `bento state` runs), so it can be piped into standard OCI inspection
+/// tooling instead of only bento's own `state.json` format.
+pub fn state(container_id: &str) -> Result<()> {
+    let oci_state = container_oci_state(container_id)?;
     println!(
-        "[Start] Loading container '{}' (PID: {})",
-        container_id, state.pid
+        "{}",
+        serde_json::to_string_pretty(&oci_state).context("Failed to serialize OCI state")?
     );
+    Ok(())
+}
 
-    // Validate that the process is actually alive
-    let container_pid = Pid::from_raw(state.pid);
-    match kill(container_pid, Signal::SIGCONT) {
-        Ok(_) => {
-            println!("[Start] Container process {} is alive", state.pid);
+/// Loads `container_id`'s state and reconciles it into [`OciState`] - the
+/// data [`state`] prints and [`crate::api`]'s inspect endpoint serves over
+/// the socket.
+pub(crate) fn container_oci_state(container_id: &str) -> Result<OciState> {
+    let state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+    Ok(OciState::from_container_state(&state))
+}
+
+/// Runs `args` inside an already-running container by joining its init
+/// process's namespaces - the "tenant" path, as opposed to the PID 1 init
+/// path `exec_user_command` takes during `bento start`. Lets users do
+/// `bento exec <id> /bin/sh` against a live container for debugging.
+///
+/// `env` is handed to [`setup_container_environment`] as extra `KEY=value`
+/// entries, and `tty` allocates a pty and attaches it to the exec'd process
+/// as its controlling terminal, relaying bytes to/from the caller's own
+/// stdio - the same shape `docker exec -it` takes.
+pub fn exec_container(container_id: &str, args: &[String], env: &[String], tty: bool) -> Result<()> {
+    let state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+
+    state
+        .status
+        .guard(state.status.can_exec(), "exec")
+        .with_context(|| format!("Container '{container_id}'"))?;
+
+    let target_pid = Pid::from_raw(state.pid);
+
+    // Read the container's seccomp policy (if any) from its bundle's
+    // `config.json` while the bundle path is still reachable: once
+    // `join_namespaces` below puts us in the container's own mount
+    // namespace, the host path the bundle lives at is no longer visible.
+    let seccomp = load_seccomp_policy(&state.bundle_path).unwrap_or_else(|e| {
+        eprintln!("[Exec] Warning: Failed to load seccomp policy: {e}");
+        None
+    });
+
+    // Join every namespace of the target first: a joined pid namespace only
+    // takes effect for children created *after* the setns(2) call, so this
+    // has to happen before the fork below rather than inside the child.
+    join_namespaces(target_pid).with_context(|| {
+        format!("Failed to join namespaces of container '{container_id}' (PID {target_pid})")
+    })?;
+
+    let pty = if tty {
+        Some(openpty(None, None).context("Failed to allocate pty for exec session")?)
+    } else {
+        None
+    };
+
+    match unsafe { fork() } {
+        Ok(ForkResult::Parent { child }) => {
+            // Join the container's own cgroup (not just its cgroup
+            // *namespace*, which `join_namespaces` already did) so resource
+            // limits and accounting cover this exec'd process too.
+            match cgroup_freezer_backend(container_id) {
+                Ok(cgroup) => {
+                    if let Err(e) = cgroup.add_process(child) {
+                        eprintln!("[Exec] Warning: Failed to join container cgroup: {e}");
+                    }
+                }
+                Err(e) => eprintln!("[Exec] Warning: Failed to resolve container cgroup: {e}"),
+            }
+
+            if let Some(pty) = pty {
+                drop(pty.slave); // parent only talks to the master end
+                if let Err(e) = relay_pty(pty.master.as_raw_fd()) {
+                    eprintln!("[Exec] Warning: pty relay ended early: {e}");
+                }
+            }
+
+            match waitpid(child, None)? {
+                WaitStatus::Exited(_, code) if code != 0 => {
+                    Err(anyhow!("Command exited with status {code}"))
+                }
+                WaitStatus::Signaled(_, signal, _) => {
+                    Err(anyhow!("Command was killed by signal {signal:?}"))
+                }
+                _ => Ok(()),
+            }
         }
-        Err(_) => {
-            // Process is dead - clean up and fail
-            println!(
-                "[Start] Container process {} is dead, cleaning up",
-                state.pid
-            );
-            state.status = "stopped".to_string();
-            save_container_state(container_id, &state)?;
-            return Err(anyhow!("Container process {} no longer exists", state.pid));
+        Ok(ForkResult::Child) => {
+            if let Some(pty) = pty {
+                drop(pty.master); // child only talks to the slave end
+                if let Err(e) = attach_controlling_tty(pty.slave.as_raw_fd()) {
+                    eprintln!("[Exec] Failed to attach controlling tty: {e}");
+                    std::process::exit(1);
+                }
+            }
+
+            if let Err(e) = setup_container_environment(env) {
+                eprintln!("[Exec] Failed to setup environment: {e}");
+                std::process::exit(1);
+            }
+
+            std::process::exit(run_under_seccomp(seccomp, || exec_args(args)) as i32);
         }
+        Err(e) => Err(anyhow!("Failed to fork exec process: {e}")),
     }
+}
 
-    // Check container state - handle inconsistent states
-    if state.status == "running" {
-        // Process is alive but state says running - check if actually running
-        println!("[Start] Container claims to be running, verifying...");
-        return Err(anyhow!(
-            "Container '{}' appears to already be running (PID: {}). Use 'kill' to stop it first.",
-            container_id,
-            state.pid
-        ));
+/// Reads `bundle_path`'s `config.json` and returns its `linux.seccomp`
+/// policy, if any. Callers must do this while the bundle path is still
+/// reachable - i.e. before `fs::prepare_rootfs` pivots init's mount
+/// namespace, or before `exec_container` joins the target container's mount
+/// namespace via `setns`.
+fn load_seccomp_policy(bundle_path: &str) -> Result<Option<crate::config2::SeccompConfig>> {
+    let config_json = Path::new(bundle_path).join("config.json");
+    let oci = crate::config::Config::load(&config_json)
+        .with_context(|| format!("Failed to load OCI config from {}", config_json.display()))?;
+    Ok(oci.linux.seccomp)
+}
+
+/// Loads `seccomp` (if any) into the kernel for the calling (about-to-exec)
+/// process. Returns the notify fd from
+/// [`crate::seccomp::SeccompFilter::apply`] when the policy uses
+/// `SCMP_ACT_NOTIFY`, for [`run_under_seccomp`] to hand off to a supervisor.
+fn apply_seccomp_filter(seccomp: crate::config2::SeccompConfig) -> Result<Option<RawFd>> {
+    crate::seccomp::SeccompFilter::new(seccomp)
+        .apply()
+        .context("Failed to load seccomp filter into kernel")
+}
+
+/// Loads `seccomp` (if any) into the calling process and then runs `run` -
+/// the container command's own `exec`.
+///
+/// A policy with no `SCMP_ACT_NOTIFY` rules is loaded in place and `run`
+/// executes directly, same as before. No policy (or one that fails to load)
+/// is treated as "nothing extra to enforce" (most bundles don't ship one)
+/// rather than a hard failure of the whole exec.
+///
+/// A policy that *does* use `SCMP_ACT_NOTIFY` needs a supervisor holding the
+/// notify fd and responding to intercepted syscalls for as long as `run`'s
+/// process is alive - and that can't be the same process that's about to
+/// call `run`, since `execve` drops every thread but the caller's along with
+/// any chance of going back to polling the fd. So this forks - but unlike a
+/// typical worker fork, the *caller* (already PID 1 inside the container's
+/// pid namespace, by the time this is reached from `init_handler_with_pause`)
+/// has to stay the one that calls `run`, or the container's real command
+/// ends up as PID 2 and never sees the SIGTERM `signal_container` sends to
+/// PID 1. So the new child is the one that becomes the supervisor - it
+/// inherits the already-loaded filter (seccomp filters survive `fork`) but
+/// never execs, it just services notifications until the fd errors out
+/// (the exec'd sibling holding the filter has exited) and exits. The caller
+/// closes its own copy of the notify fd and calls `run` directly, keeping
+/// its pid.
+fn run_under_seccomp(
+    seccomp: Option<crate::config2::SeccompConfig>,
+    run: impl FnOnce() -> isize,
+) -> isize {
+    let notify_fd = match seccomp {
+        Some(seccomp) => match apply_seccomp_filter(seccomp) {
+            Ok(fd) => fd,
+            Err(e) => {
+                println!("[Seccomp] No filter applied: {e}");
+                None
+            }
+        },
+        None => None,
+    };
+
+    let notify_fd = match notify_fd {
+        Some(fd) => fd,
+        None => return run(),
+    };
+
+    match unsafe { fork() } {
+        Ok(ForkResult::Parent { child: _ }) => {
+            let _ = nix::unistd::close(notify_fd); // the forked child supervises, not us
+            run()
+        }
+        Ok(ForkResult::Child) => {
+            crate::seccomp::SeccompFilter::supervise_notifications(notify_fd);
+            let _ = nix::unistd::close(notify_fd);
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("[Seccomp] Failed to fork notify supervisor: {e}");
+            let _ = nix::unistd::close(notify_fd);
+            run()
+        }
     }
+}
 
-    if state.status != "created" {
+/// Makes the pty behind `slave_fd` the calling process's controlling
+/// terminal: starts a new session (a process can only acquire a controlling
+/// tty as a session leader), then `TIOCSCTTY` and wires it up as
+/// stdin/stdout/stderr.
+fn attach_controlling_tty(slave_fd: RawFd) -> Result<()> {
+    nix::unistd::setsid().context("Failed to start a new session for the exec pty")?;
+
+    // SAFETY: slave_fd is a valid, open fd for the lifetime of this call.
+    let rc = unsafe { libc::ioctl(slave_fd, libc::TIOCSCTTY as _, 0) };
+    if rc != 0 {
         return Err(anyhow!(
-            "Container '{}' is not in 'created' state (current: {})",
-            container_id,
-            state.status
+            "ioctl(TIOCSCTTY) failed: {}",
+            std::io::Error::last_os_error()
         ));
     }
 
-    // Send start signal via pipe
-    let start_pipe_path = state
-        .start_pipe_path
+    nix::unistd::dup2(slave_fd, 0).context("Failed to dup2 pty slave onto stdin")?;
+    nix::unistd::dup2(slave_fd, 1).context("Failed to dup2 pty slave onto stdout")?;
+    nix::unistd::dup2(slave_fd, 2).context("Failed to dup2 pty slave onto stderr")?;
+    Ok(())
+}
+
+/// Shuttles bytes between the caller's own stdio and `master_fd` until the
+/// exec'd process closes its end of the pty (read returning 0), the way an
+/// interactive `docker exec -it` session does.
+fn relay_pty(master_fd: RawFd) -> Result<()> {
+    use nix::poll::{PollFd, PollFlags, PollTimeout, poll};
+    use std::io::{Read, Write};
+    use std::os::fd::BorrowedFd;
+
+    let stdin_fd = std::io::stdin().as_raw_fd();
+    let mut master = unsafe { std::fs::File::from_raw_fd(master_fd) };
+    let mut buf = [0u8; 4096];
+
+    loop {
+        let master_borrow = unsafe { BorrowedFd::borrow_raw(master_fd) };
+        let stdin_borrow = unsafe { BorrowedFd::borrow_raw(stdin_fd) };
+        let mut fds = [
+            PollFd::new(master_borrow, PollFlags::POLLIN),
+            PollFd::new(stdin_borrow, PollFlags::POLLIN),
+        ];
+
+        if poll(&mut fds, PollTimeout::NONE)? <= 0 {
+            continue;
+        }
+
+        if fds[0]
+            .revents()
+            .is_some_and(|r| r.intersects(PollFlags::POLLIN | PollFlags::POLLHUP))
+        {
+            match master.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(len) => {
+                    std::io::stdout().write_all(&buf[..len])?;
+                    std::io::stdout().flush()?;
+                }
+            }
+        }
+
+        if fds[1]
+            .revents()
+            .is_some_and(|r| r.contains(PollFlags::POLLIN))
+        {
+            let len = std::io::stdin().read(&mut buf)?;
+            if len == 0 {
+                break;
+            }
+            master.write_all(&buf[..len])?;
+        }
+    }
+
+    // `master` is a view over an fd we don't own (it's `pty.master`'s,
+    // dropped separately); leak it instead of closing master_fd twice.
+    std::mem::forget(master);
+    Ok(())
+}
+
+pub fn start_container(container_id: &str) -> Result<()> {
+    // Load container state
+    let mut state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+
+    println!(
+        "[Start] Loading container '{}' (PID: {})",
+        container_id, state.pid
+    );
+
+    // Check container state - the transition table is the single source of
+    // truth, so any status other than `Created` (including `Running`, which
+    // used to get its own "already running" message) is simply illegal.
+    state
+        .status
+        .guard(state.status.can_start(), "start")
+        .with_context(|| format!("Container '{container_id}' (PID: {})", state.pid))?;
+
+    // Connect to init's notify socket and send the start request. A dead
+    // init won't have anything listening, so `connect` failing here stands
+    // in for what used to be a separate `SIGCONT` liveness probe.
+    let socket_path = state
+        .notify_socket_path
         .as_ref()
-        .ok_or_else(|| anyhow!("No start pipe path in container state"))?;
+        .ok_or_else(|| anyhow!("No notify socket path in container state"))?;
 
     // Convert container path to host path
     let home = std::env::var("HOME")?;
-    let host_pipe_path = format!(
+    let host_socket_path = format!(
         "{}/.local/share/bento/{}/rootfs{}",
-        home, container_id, start_pipe_path
+        home, container_id, socket_path
     );
 
-    println!("[Start] Sending start signal via: {}", host_pipe_path);
-
-    // Open and write to the named pipe with error handling
-    match std::fs::OpenOptions::new()
-        .write(true)
-        .open(&host_pipe_path)
-    {
-        Ok(mut pipe) => {
-            use std::io::Write;
-
-            // Write the complete start signal
-            match pipe.write_all(b"start") {
-                Ok(_) => {
-                    // Ensure data reaches the pipe
-                    pipe.flush().context("Failed to flush start signal")?;
-                    println!("[Start] Successfully sent complete start signal");
-                }
-                Err(e) => {
-                    return Err(anyhow!("Failed to write start signal: {}", e));
-                }
-            }
-        }
-        Err(e) => {
-            return Err(anyhow!(
-                "Failed to open start pipe {}: {}",
-                host_pipe_path,
-                e
-            ));
-        }
+    println!("[Start] Connecting to notify socket: {}", host_socket_path);
+
+    let fd = socket(AddressFamily::Unix, SockType::SeqPacket, SockFlag::empty(), None)
+        .map_err(|e| anyhow!("Failed to create notify socket client: {e}"))?;
+    let addr = UnixAddr::new(host_socket_path.as_str())
+        .with_context(|| format!("Invalid notify socket path {host_socket_path}"))?;
+
+    if connect(fd.as_raw_fd(), &addr).is_err() {
+        println!(
+            "[Start] Container process {} is dead, cleaning up",
+            state.pid
+        );
+        state.status = ContainerStatus::Stopped;
+        save_container_state(container_id, &state)?;
+        return Err(anyhow!("Container process {} no longer exists", state.pid));
+    }
+
+    send(fd.as_raw_fd(), START_REQUEST, MsgFlags::empty())
+        .context("Failed to send start request")?;
+
+    let mut response = [0u8; 256];
+    let n = recv(fd.as_raw_fd(), &mut response, MsgFlags::empty())
+        .context("Failed to read acknowledgment from init")?;
+    let response = &response[..n];
+
+    if let Some(reason) = response.strip_prefix(b"error: ") {
+        return Err(anyhow!(
+            "Container init reported a startup error: {}",
+            String::from_utf8_lossy(reason)
+        ));
+    }
+    if response != START_ACK {
+        return Err(anyhow!(
+            "Unexpected response from init on notify socket: {:?}",
+            String::from_utf8_lossy(response)
+        ));
     }
 
+    println!("[Start] Start request acknowledged by init");
+
     // Update container state to running
-    state.status = "running".to_string();
+    state.record_event(LifecycleEvent::Started);
     save_container_state(container_id, &state)
         .context("Failed to update container state after start")?;
 
-    // Clean up the named pipe from host perspective
-    let _ = std::fs::remove_file(&host_pipe_path);
+    // Clean up the socket file from the host perspective
+    let _ = std::fs::remove_file(&host_socket_path);
 
     println!("[Start] Container '{}' is now running", container_id);
     Ok(())
 }
 
+// ============================================================================
+// SIGNAL FORWARDING AND GRACEFUL SHUTDOWN
+// ============================================================================
+
+/// How long to wait after forwarding the requested signal before escalating
+/// to SIGKILL.
+const KILL_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+/// Forwards `signal` (default `SIGTERM`) to a container's init process,
+/// waiting up to [`KILL_GRACE_PERIOD`] for it to exit before escalating to
+/// `SIGKILL`. Reaps init, records its exit status in `state.json`, and
+/// cleans up the notify socket.
+pub fn stop_container(container_id: &str, signal: Option<&str>) -> Result<()> {
+    let mut state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+
+    let signal = match signal {
+        Some(s) => parse_signal(s)?,
+        None => Signal::SIGTERM,
+    };
+    state
+        .status
+        .guard(state.status.can_kill(), "kill")
+        .with_context(|| format!("Container '{container_id}'"))?;
+
+    let init_pid = Pid::from_raw(state.pid);
+
+    println!("[Kill] Forwarding {signal:?} to container '{container_id}' (init PID {init_pid})");
+
+    // Ignoring SIGTERM/SIGINT here means a Ctrl-C on `bento kill` itself
+    // can't abort us partway through the forward/escalate/reap sequence and
+    // leave state.json stale.
+    ignore_termination_signals()?;
+
+    match kill(init_pid, signal) {
+        Ok(_) => {}
+        Err(nix::errno::Errno::ESRCH) => {
+            println!("[Kill] Container '{container_id}' init process is already gone");
+            state.status = ContainerStatus::Stopped;
+            save_container_state(container_id, &state)?;
+            cleanup_notify_socket(container_id)?;
+            return Ok(());
+        }
+        Err(e) => return Err(anyhow!("Failed to signal init process {init_pid}: {e}")),
+    }
+
+    let wait_status = wait_for_exit_with_grace(init_pid, KILL_GRACE_PERIOD)?;
+    record_exit(&mut state, &wait_status);
+
+    save_container_state(container_id, &state)
+        .context("Failed to update container state after kill")?;
+    cleanup_notify_socket(container_id)?;
+
+    println!("[Kill] Container '{container_id}' stopped ({wait_status:?})");
+    Ok(())
+}
+
+/// Delivers `signal` to a running container's init process without tearing
+/// anything down - the plain OCI `kill <id> <signal>` contract, as opposed to
+/// [`stop_container`]'s full stop-wait-reap-cleanup sequence. Leaves
+/// `state.json` and the notify socket untouched; it's up to the signal (and
+/// the container's own process 1) to decide whether this is terminal.
+pub fn signal_container(container_id: &str, signal: &str) -> Result<()> {
+    let state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+
+    state
+        .status
+        .guard(state.status.can_kill(), "kill")
+        .with_context(|| format!("Container '{container_id}'"))?;
+
+    let sig = parse_signal(signal)?;
+    let init_pid = Pid::from_raw(state.pid);
+
+    println!("[Kill] Sending {sig:?} to container '{container_id}' (init PID {init_pid})");
+
+    match kill(init_pid, sig) {
+        Ok(_) => Ok(()),
+        Err(nix::errno::Errno::ESRCH) => Err(anyhow!(
+            "Container '{container_id}' init process is already gone"
+        )),
+        Err(e) => Err(anyhow!("Failed to signal init process {init_pid}: {e}")),
+    }
+}
+
+/// Freezes every task in a running container's cgroup via [`cgroup_freezer_backend`].
+pub fn pause_container(container_id: &str) -> Result<()> {
+    let mut state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+
+    state
+        .status
+        .guard(state.status.can_pause(), "pause")
+        .with_context(|| format!("Container '{container_id}'"))?;
+
+    let backend = cgroup_freezer_backend(container_id)?;
+    backend
+        .freeze(crate::cgroups::FreezerState::Frozen)
+        .with_context(|| format!("Failed to freeze container '{container_id}'"))?;
+
+    state.record_event(LifecycleEvent::Paused);
+    save_container_state(container_id, &state)
+        .context("Failed to update container state after pause")?;
+
+    println!("[Pause] Container '{container_id}' is now paused");
+    Ok(())
+}
+
+/// Thaws a container previously frozen by [`pause_container`].
+pub fn resume_container(container_id: &str) -> Result<()> {
+    let mut state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+
+    state
+        .status
+        .guard(state.status.can_resume(), "resume")
+        .with_context(|| format!("Container '{container_id}'"))?;
+
+    let backend = cgroup_freezer_backend(container_id)?;
+    backend
+        .freeze(crate::cgroups::FreezerState::Thawed)
+        .with_context(|| format!("Failed to thaw container '{container_id}'"))?;
+
+    state.record_event(LifecycleEvent::Resumed);
+    save_container_state(container_id, &state)
+        .context("Failed to update container state after resume")?;
+
+    println!("[Resume] Container '{container_id}' is now running");
+    Ok(())
+}
+
+/// Removes a stopped container: tears down its cgroup, network setup and
+/// notify socket, then deletes `state.json` and its per-container data
+/// directory. Stops it first (via [`stop_container`]) if it's still running
+/// or paused, the same `delete --force` leniency `bento delete` extends
+/// rather than making callers run `bento kill` first.
+pub fn delete_container(container_id: &str) -> Result<()> {
+    let mut state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+
+    if matches!(state.status, ContainerStatus::Running | ContainerStatus::Paused) {
+        stop_container(container_id, None)?;
+        state = load_container_state(container_id)
+            .with_context(|| format!("Failed to reload state for container '{}'", container_id))?;
+    }
+
+    state
+        .status
+        .guard(state.status.can_delete(), "delete")
+        .with_context(|| format!("Container '{container_id}'"))?;
+
+    match cgroup_freezer_backend(container_id) {
+        Ok(backend) => {
+            if let Err(e) = backend.cleanup() {
+                println!("[Delete] Warning: failed to clean up cgroup for '{container_id}': {e}");
+            }
+        }
+        Err(e) => println!("[Delete] Warning: failed to locate cgroup for '{container_id}': {e}"),
+    }
+
+    match state.network_mode {
+        crate::networking::NetworkMode::Veth => {
+            if let Err(e) = crate::networking::teardown_veth_network(container_id) {
+                println!(
+                    "[Delete] Warning: failed to tear down veth network for '{container_id}': {e}"
+                );
+            }
+        }
+        crate::networking::NetworkMode::Slirp4netns => {
+            if let Err(e) = crate::networking::teardown_slirp_network(container_id) {
+                println!(
+                    "[Delete] Warning: failed to tear down slirp4netns network for '{container_id}': {e}"
+                );
+            }
+        }
+        crate::networking::NetworkMode::None => {}
+    }
+
+    cleanup_notify_socket(container_id).context("Failed to clean up notify socket")?;
+
+    let state_dir = get_state_dir()?;
+    let _ = std::fs::remove_file(state_dir.join(format!("{container_id}.json")));
+
+    let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    let _ = std::fs::remove_dir_all(format!("{home}/.local/share/bento/{container_id}"));
+
+    println!("[Delete] Container '{container_id}' removed");
+    Ok(())
+}
+
+/// Builds whichever [`CgroupBackend`] actually owns `container_id`'s cgroup,
+/// mirroring the detection `cleanup_cgroups` does - pause/resume need the
+/// same v1-vs-v2 dispatch to reach the right freezer knob
+/// (`cgroup.freeze` vs `freezer.state`). On v2, reads back the
+/// [`crate::cgroups::CgroupDriver`] `setup_cgroups` used from the saved
+/// container state instead of assuming `Fs`, so a container created with
+/// the systemd driver doesn't get a second, unmanaged `Fs` cgroup created
+/// out from under systemd's scope unit.
+pub(crate) fn cgroup_freezer_backend(
+    container_id: &str,
+) -> Result<Box<dyn crate::cgroups::CgroupBackend>> {
+    use crate::cgroups::{CgroupManager, CgroupManagerV1, CgroupVersion, detect_cgroup_setup};
+
+    Ok(match detect_cgroup_setup() {
+        CgroupVersion::V2 => {
+            let driver = load_container_state(container_id)
+                .map(|state| state.cgroup_driver)
+                .unwrap_or_default();
+            Box::new(CgroupManager::with_driver(container_id.to_string(), driver)?)
+        }
+        CgroupVersion::V1 => Box::new(CgroupManagerV1::new(container_id.to_string())?),
+    })
+}
+
+// ============================================================================
+// LOGS AND ATTACH
+// ============================================================================
+
+/// Prints a container's captured stdout/stderr log, tailing it as new output
+/// arrives when `follow` is set.
+pub fn logs(container_id: &str, follow: bool) -> Result<()> {
+    stream_log(container_id, follow, false)
+}
+
+/// Connects the caller's terminal to a running container's live output,
+/// skipping everything logged before this call.
+pub fn attach(container_id: &str) -> Result<()> {
+    println!("[Attach] Streaming live output for '{container_id}' (Ctrl-C to detach)");
+    stream_log(container_id, true, true)
+}
+
+/// Shared implementation behind [`logs`] and [`attach`]: prints the
+/// container's log file (or, with `live_only`, seeks past it first) then
+/// polls for more data when `follow` is set, the way `tail -f` does.
+fn stream_log(container_id: &str, follow: bool, live_only: bool) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let state = load_container_state(container_id)
+        .with_context(|| format!("Failed to load state for container '{}'", container_id))?;
+    let log_path = state
+        .log_path
+        .ok_or_else(|| anyhow!("No log file recorded for container '{container_id}'"))?;
+
+    let mut file = std_fs::File::open(&log_path)
+        .with_context(|| format!("Failed to open container log {log_path}"))?;
+
+    if live_only {
+        file.seek(SeekFrom::End(0))
+            .with_context(|| format!("Failed to seek to end of {log_path}"))?;
+    } else {
+        let mut existing = String::new();
+        file.read_to_string(&mut existing)
+            .with_context(|| format!("Failed to read container log {log_path}"))?;
+        print!("{existing}");
+    }
+
+    if !follow {
+        return Ok(());
+    }
+
+    loop {
+        let mut chunk = String::new();
+        let n = file
+            .read_to_string(&mut chunk)
+            .with_context(|| format!("Failed to read container log {log_path}"))?;
+        if n == 0 {
+            std::thread::sleep(Duration::from_millis(200));
+            continue;
+        }
+        print!("{chunk}");
+    }
+}
+
+/// Polls `pid` with `WNOHANG` until it exits, escalating to `SIGKILL` once
+/// `grace` has elapsed since the first signal was sent.
+fn wait_for_exit_with_grace(pid: Pid, grace: Duration) -> Result<WaitStatus> {
+    let started = Instant::now();
+
+    loop {
+        match waitpid(pid, Some(WaitPidFlag::WNOHANG)) {
+            Ok(WaitStatus::StillAlive) => {
+                if started.elapsed() >= grace {
+                    println!("[Kill] Grace period elapsed, escalating to SIGKILL");
+                    let _ = kill(pid, Signal::SIGKILL);
+                    return waitpid(pid, None)
+                        .map_err(|e| anyhow!("Failed to reap init process {pid}: {e}"));
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Ok(status) => return Ok(status),
+            Err(nix::errno::Errno::ECHILD) => {
+                // Already reaped (e.g. by another process) - nothing more we
+                // can learn about how it exited.
+                return Ok(WaitStatus::Exited(pid, 0));
+            }
+            Err(e) => return Err(anyhow!("waitpid failed for init process {pid}: {e}")),
+        }
+    }
+}
+
+/// Records an init process's exit status into `state` as a [`LifecycleEvent`].
+fn record_exit(state: &mut ContainerState, status: &WaitStatus) {
+    match status {
+        WaitStatus::Exited(_, code) => {
+            state.record_event(LifecycleEvent::Exited { code: *code });
+        }
+        WaitStatus::Signaled(_, signal, _) => {
+            state.record_event(LifecycleEvent::Signaled {
+                signal: *signal as i32,
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Ignores SIGTERM/SIGINT in the current process for the remainder of a
+/// `stop_container` call.
+fn ignore_termination_signals() -> Result<()> {
+    unsafe {
+        nix::sys::signal::signal(Signal::SIGTERM, SigHandler::SigIgn)
+            .map_err(|e| anyhow!("Failed to ignore SIGTERM: {}", e))?;
+        nix::sys::signal::signal(Signal::SIGINT, SigHandler::SigIgn)
+            .map_err(|e| anyhow!("Failed to ignore SIGINT: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Parses a signal name (`"TERM"`, `"SIGTERM"`, case-insensitive) or raw
+/// number into a [`Signal`].
+fn parse_signal(input: &str) -> Result<Signal> {
+    let normalized = input.trim().to_uppercase();
+    let name = normalized.strip_prefix("SIG").unwrap_or(&normalized);
+
+    match name {
+        "TERM" => Ok(Signal::SIGTERM),
+        "KILL" => Ok(Signal::SIGKILL),
+        "INT" => Ok(Signal::SIGINT),
+        "HUP" => Ok(Signal::SIGHUP),
+        "QUIT" => Ok(Signal::SIGQUIT),
+        "USR1" => Ok(Signal::SIGUSR1),
+        "USR2" => Ok(Signal::SIGUSR2),
+        "STOP" => Ok(Signal::SIGSTOP),
+        "CONT" => Ok(Signal::SIGCONT),
+        _ => normalized
+            .parse::<i32>()
+            .ok()
+            .and_then(|n| Signal::try_from(n).ok())
+            .ok_or_else(|| anyhow!("Unknown signal: {}", input)),
+    }
+}
+
 /*
 fn read_start_signal(pipe_path: &str) -> Result<()> {
     use std::io::Read;
@@ -945,21 +2084,22 @@ fn send_start_signal(pipe_path: &str) -> Result<()> {
     Ok(())
 }
 */
-pub fn cleanup_named_pipes(container_id: &str) -> Result<()> {
+pub fn cleanup_notify_socket(container_id: &str) -> Result<()> {
     let home = std::env::var("HOME").context("HOME environment variable not set")?;
+    let container_relative_path = notify_socket_path(container_id);
 
-    let pipe_paths = [
-        format!("/tmp/bento-start-{}", container_id),
+    let socket_paths = [
+        container_relative_path.clone(),
         format!(
-            "{}/.local/share/bento/{}/rootfs/tmp/bento-start-{}",
-            home, container_id, container_id
+            "{}/.local/share/bento/{}/rootfs{}",
+            home, container_id, container_relative_path
         ),
     ];
 
-    for path in &pipe_paths {
+    for path in &socket_paths {
         if Path::new(path).exists() {
             match std::fs::remove_file(path) {
-                Ok(_) => println!("[Cleanup] Removed stale named pipe: {}", path),
+                Ok(_) => println!("[Cleanup] Removed stale notify socket: {}", path),
                 Err(e) => println!("[Cleanup] Warning: Failed to remove {}: {}", path, e),
             }
         }
@@ -979,15 +2119,6 @@ pub struct ContainerInfo {
     pub runtime_status: RuntimeStatus,
 }
 
-/// Container status enumeration
-#[derive(Debug, Clone)]
-pub enum ContainerStatus {
-    Created,
-    Running,
-    Stopped,
-    Paused,
-}
-
 /// Runtime status based on actual process state
 #[derive(Debug, Clone)]
 pub enum RuntimeStatus {
@@ -1004,14 +2135,10 @@ impl ContainerInfo {
             Ok(_) => RuntimeStatus::Alive,
             Err(_) => RuntimeStatus::Dead,
         };
-
-        let status = match state.status.as_str() {
-            "created" => ContainerStatus::Created,
-            "running" => ContainerStatus::Running,
-            "stopped" => ContainerStatus::Stopped,
-            "paused" => ContainerStatus::Paused,
-            _ => ContainerStatus::Created, // Default fallback
-        };
+        // Same reconciliation `OciState::from_container_state` does, so
+        // `bento list` and `bento state` never disagree about a container
+        // that died without `bento kill` reaping it.
+        let status = reconcile_status(state.status, matches!(runtime_status, RuntimeStatus::Alive));
 
         Ok(Self {
             id: state.id,
@@ -1023,14 +2150,14 @@ impl ContainerInfo {
         })
     }
 
-    /// Display status combining container status and runtime status
+    /// Display status, already reconciled against liveness in [`Self::from_state`].
     pub fn display_status(&self) -> String {
-        match (&self.status, &self.runtime_status) {
-            (ContainerStatus::Running, RuntimeStatus::Alive) => "running".to_string(),
-            (ContainerStatus::Created, RuntimeStatus::Alive) => "created".to_string(),
-            (_, RuntimeStatus::Dead) => "stopped".to_string(),
-            (ContainerStatus::Paused, RuntimeStatus::Alive) => "paused".to_string(),
-            _ => "unknown".to_string(),
+        match self.status {
+            ContainerStatus::Creating => "creating".to_string(),
+            ContainerStatus::Created => "created".to_string(),
+            ContainerStatus::Running => "running".to_string(),
+            ContainerStatus::Paused => "paused".to_string(),
+            ContainerStatus::Stopped => "stopped".to_string(),
         }
     }
 }
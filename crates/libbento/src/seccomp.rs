@@ -1,6 +1,10 @@
-use crate::config2::SeccompConfig;
+use crate::config2::{ArgCompare, SeccompConfig};
 use anyhow::{Context, Result};
-use libseccomp::{ScmpAction, ScmpArch, ScmpFilterContext, ScmpSyscall};
+use libseccomp::{
+    ScmpAction, ScmpArch, ScmpArgCompare, ScmpCompareOp, ScmpFilterContext, ScmpNotifReq,
+    ScmpNotifResp, ScmpNotifRespFlags, ScmpSyscall,
+};
+use std::os::unix::io::RawFd;
 
 // this holds the configurations from seccompConfig, which defines the filtering rules.
 pub struct SeccompFilter {
@@ -13,11 +17,29 @@ impl SeccompFilter {
         Self { config }
     }
     // the execution starts from here
-    pub fn apply(&self) -> Result<()> {
+    //
+    // Returns the notify fd from `ScmpFilterContext::get_notify_fd` when the
+    // filter contains `SCMP_ACT_NOTIFY` rules, so a supervisor thread can
+    // `ScmpNotifReq::receive` on it and `ScmpNotifResp::respond` to each
+    // intercepted syscall. The fd must outlive the container process - the
+    // caller is responsible for keeping it open and for polling it.
+    pub fn apply(&self) -> Result<Option<RawFd>> {
         self.validate_config()?; // This is to validate the actual config.
         let default_action = self
             .parse_action(&self.config.default_action)
             .context("Invalid default action")?;
+
+        let has_notify_rules = self
+            .config
+            .syscalls
+            .iter()
+            .any(|rule| rule.action == "SCMP_ACT_NOTIFY");
+        if has_notify_rules && matches!(default_action, ScmpAction::Notify) {
+            anyhow::bail!(
+                "Invalid seccomp config: default action and a syscall rule can't both be SCMP_ACT_NOTIFY"
+            );
+        }
+
         let mut ctx = ScmpFilterContext::new_filter(default_action)
             .context("Failed to initialize seccomp filter")?;
 
@@ -28,7 +50,15 @@ impl SeccompFilter {
         ctx.load()
             .context("Failed to load seccomp filter into kernel")?;
         println!("Filter program loaded into the kernel successfully.");
-        Ok(())
+
+        if has_notify_rules {
+            let notify_fd = ctx
+                .get_notify_fd()
+                .context("Failed to get seccomp notify fd")?;
+            Ok(Some(notify_fd))
+        } else {
+            Ok(None)
+        }
     }
 
     fn parse_action(&self, action: &str) -> Result<ScmpAction> {
@@ -39,10 +69,41 @@ impl SeccompFilter {
             "SCMP_ACT_ALLOW" => Ok(ScmpAction::Allow),
             "SCMP_ACT_TRAP" => Ok(ScmpAction::Trap),
             "SCMP_ACT_TRACE" => Ok(ScmpAction::Trace(0)),
+            "SCMP_ACT_NOTIFY" => Ok(ScmpAction::Notify),
             _ => anyhow::bail!("Invalid action: {}", action),
         }
     }
 
+    fn parse_compare_op(&self, op: &str) -> Result<ScmpCompareOp> {
+        match op {
+            "SCMP_CMP_NE" => Ok(ScmpCompareOp::NotEqual),
+            "SCMP_CMP_LT" => Ok(ScmpCompareOp::Less),
+            "SCMP_CMP_LE" => Ok(ScmpCompareOp::LessOrEqual),
+            "SCMP_CMP_EQ" => Ok(ScmpCompareOp::Equal),
+            "SCMP_CMP_GE" => Ok(ScmpCompareOp::GreaterEqual),
+            "SCMP_CMP_GT" => Ok(ScmpCompareOp::Greater),
+            "SCMP_CMP_MASKED_EQ" => Ok(ScmpCompareOp::MaskedEqual(0)),
+            _ => anyhow::bail!("Invalid arg compare op: {op}"),
+        }
+    }
+
+    fn build_arg_compare(&self, arg: &ArgCompare) -> Result<ScmpArgCompare> {
+        let op = self.parse_compare_op(&arg.op)?;
+        // `ScmpCompareOp::MaskedEqual` carries the mask itself rather than
+        // taking it as a separate comparison value, so for that op
+        // `value` is the mask and `value_two` (defaulting to the mask,
+        // i.e. a plain equality check under that mask) is the datum.
+        let op = match op {
+            ScmpCompareOp::MaskedEqual(_) => ScmpCompareOp::MaskedEqual(arg.value),
+            other => other,
+        };
+        let datum = match op {
+            ScmpCompareOp::MaskedEqual(_) => arg.value_two.unwrap_or(arg.value),
+            _ => arg.value,
+        };
+        Ok(ScmpArgCompare::new(arg.index, op, datum))
+    }
+
     // this is helful to validate config - instead of exiting when there's 0 arch
     fn validate_config(&self) -> Result<()> {
         if self.config.architectures.is_empty() {
@@ -81,17 +142,56 @@ impl SeccompFilter {
         Ok(())
     }
 
+    /// Runs a minimal `SCMP_ACT_NOTIFY` supervisor loop on `notify_fd`,
+    /// letting every intercepted syscall continue unmodified - bento doesn't
+    /// second-guess the bundle's own rules, it just makes the `NOTIFY`
+    /// action observable instead of silently dropping the notification.
+    /// Returns once `notify_fd` is closed (the supervised process exited) or
+    /// a `receive`/`respond` call errors for any other reason.
+    ///
+    /// The caller must run this from a *different* process than the one the
+    /// filter is loaded into: `execve` only preserves the calling thread, so
+    /// whoever loaded the filter can't also block on its own notify fd once
+    /// it execs the container command.
+    pub fn supervise_notifications(notify_fd: RawFd) {
+        loop {
+            let req = match ScmpNotifReq::receive(notify_fd) {
+                Ok(req) => req,
+                Err(_) => break,
+            };
+
+            let resp = ScmpNotifResp::new(req.id, 0, 0, ScmpNotifRespFlags::CONTINUE.bits());
+            if resp.respond(notify_fd).is_err() {
+                break;
+            }
+        }
+    }
+
     fn add_syscall_rules(&self, ctx: &mut ScmpFilterContext) -> Result<()> {
         for rule in &self.config.syscalls {
             let action = self
                 .parse_action(&rule.action)
                 .with_context(|| format!("Invalid action in rule: {}", rule.action))?;
 
+            let arg_compares = rule
+                .args
+                .iter()
+                .map(|arg| self.build_arg_compare(arg))
+                .collect::<Result<Vec<_>>>()
+                .with_context(|| format!("Invalid arg constraint in rule: {}", rule.action))?;
+
             for syscall_name in &rule.names {
                 match ScmpSyscall::from_name(syscall_name.as_str()) {
                     Ok(syscall) => {
-                        ctx.add_rule(action, syscall)
-                            .with_context(|| format!("Failed to add rule for {syscall_name}"))?;
+                        if arg_compares.is_empty() {
+                            ctx.add_rule(action, syscall)
+                                .with_context(|| format!("Failed to add rule for {syscall_name}"))?;
+                        } else {
+                            ctx.add_rule_conditional(action, syscall, &arg_compares)
+                                .with_context(|| {
+                                    format!("Failed to add conditional rule for {syscall_name}")
+                                })?;
+                        }
                         println!("Added rule successfully : {syscall_name} -> {:?}", action);
                     }
                     Err(_) => {
@@ -103,3 +203,100 @@ impl SeccompFilter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter() -> SeccompFilter {
+        SeccompFilter::new(SeccompConfig {
+            default_action: "SCMP_ACT_ALLOW".to_string(),
+            architectures: vec!["SCMP_ARCH_X86_64".to_string()],
+            syscalls: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn test_build_arg_compare_plain_ops() {
+        let f = filter();
+        let cmp = f
+            .build_arg_compare(&ArgCompare {
+                index: 0,
+                value: 9,
+                value_two: None,
+                op: "SCMP_CMP_EQ".to_string(),
+            })
+            .unwrap();
+        assert_eq!(cmp, ScmpArgCompare::new(0, ScmpCompareOp::Equal, 9));
+    }
+
+    #[test]
+    fn test_build_arg_compare_masked_equal_uses_value_as_mask() {
+        let f = filter();
+        let cmp = f
+            .build_arg_compare(&ArgCompare {
+                index: 2,
+                value: 0xff,
+                value_two: Some(0x10),
+                op: "SCMP_CMP_MASKED_EQ".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            cmp,
+            ScmpArgCompare::new(2, ScmpCompareOp::MaskedEqual(0xff), 0x10)
+        );
+    }
+
+    #[test]
+    fn test_build_arg_compare_masked_equal_defaults_datum_to_mask() {
+        let f = filter();
+        let cmp = f
+            .build_arg_compare(&ArgCompare {
+                index: 1,
+                value: 0x0f,
+                value_two: None,
+                op: "SCMP_CMP_MASKED_EQ".to_string(),
+            })
+            .unwrap();
+        assert_eq!(
+            cmp,
+            ScmpArgCompare::new(1, ScmpCompareOp::MaskedEqual(0x0f), 0x0f)
+        );
+    }
+
+    #[test]
+    fn test_build_arg_compare_rejects_unknown_op() {
+        let f = filter();
+        assert!(
+            f.build_arg_compare(&ArgCompare {
+                index: 0,
+                value: 1,
+                value_two: None,
+                op: "SCMP_CMP_BOGUS".to_string(),
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_action_known_and_unknown() {
+        let f = filter();
+        assert!(matches!(f.parse_action("SCMP_ACT_ALLOW").unwrap(), ScmpAction::Allow));
+        assert!(matches!(f.parse_action("SCMP_ACT_NOTIFY").unwrap(), ScmpAction::Notify));
+        assert!(f.parse_action("SCMP_ACT_BOGUS").is_err());
+    }
+
+    #[test]
+    fn test_apply_rejects_notify_as_both_default_and_rule_action() {
+        let f = SeccompFilter::new(SeccompConfig {
+            default_action: "SCMP_ACT_NOTIFY".to_string(),
+            architectures: vec!["SCMP_ARCH_X86_64".to_string()],
+            syscalls: vec![crate::config2::SyscallRule {
+                names: vec!["read".to_string()],
+                action: "SCMP_ACT_NOTIFY".to_string(),
+                args: Vec::new(),
+            }],
+        });
+        assert!(f.apply().is_err());
+    }
+}
@@ -2,11 +2,12 @@
 
 use anyhow::{Result, anyhow};
 use libc;
-use nix::sched::{CloneFlags, clone, unshare};
+use nix::sched::{CloneFlags, clone, setns, unshare};
 use nix::sys::wait::waitpid;
 use nix::unistd::{ForkResult, Pid, execvp, fork, getgid, getpid, getuid, sethostname};
 use std::ffi::CString;
 use std::fs;
+use std::fs::File;
 use std::process::Command;
 
 use crate::process::Config;
@@ -87,14 +88,42 @@ pub fn unshare_user_namespace() -> Result<()> {
 }
 
 /// Phase 2: Create remaining namespaces (requires CAP_SYS_ADMIN from UID mapping)
-/// This can only be done after the parent has mapped UID/GID
-pub fn unshare_remaining_namespaces() -> Result<()> {
-    let flags = CloneFlags::CLONE_NEWPID | CloneFlags::CLONE_NEWUTS | CloneFlags::CLONE_NEWNS;
+/// This can only be done after the parent has mapped UID/GID.
+///
+/// `namespaces` comes from the OCI bundle's `linux.namespaces`; the user
+/// namespace is excluded even if listed since phase 1 already unshared it.
+/// An empty list falls back to bento's historical default of PID+UTS+mount.
+pub fn unshare_remaining_namespaces(namespaces: &[crate::config::NamespaceType]) -> Result<()> {
+    let flags: CloneFlags = namespaces
+        .iter()
+        .filter(|ns| !matches!(ns, crate::config::NamespaceType::User))
+        .map(namespace_clone_flag)
+        .fold(CloneFlags::empty(), |acc, flag| acc | flag);
+
+    let flags = if flags.is_empty() {
+        CloneFlags::CLONE_NEWPID | CloneFlags::CLONE_NEWUTS | CloneFlags::CLONE_NEWNS
+    } else {
+        flags
+    };
+
     unshare(flags).map_err(|e| anyhow!("Failed to unshare remaining namespaces: {}", e))?;
     println!("[Bridge] Created remaining namespaces: {flags:?}");
     Ok(())
 }
 
+fn namespace_clone_flag(ns: &crate::config::NamespaceType) -> CloneFlags {
+    use crate::config::NamespaceType;
+    match ns {
+        NamespaceType::Pid => CloneFlags::CLONE_NEWPID,
+        NamespaceType::Net => CloneFlags::CLONE_NEWNET,
+        NamespaceType::Mnt => CloneFlags::CLONE_NEWNS,
+        NamespaceType::Uts => CloneFlags::CLONE_NEWUTS,
+        NamespaceType::Ipc => CloneFlags::CLONE_NEWIPC,
+        NamespaceType::Cgroup => CloneFlags::CLONE_NEWCGROUP,
+        NamespaceType::User => CloneFlags::empty(), // handled by unshare_user_namespace
+    }
+}
+
 // ============================================================================
 // USER NAMESPACE AND UID/GID MAPPING
 // ============================================================================
@@ -182,3 +211,39 @@ pub fn map_user_namespace_rootless(child_pid: Pid) -> Result<()> {
     println!("[Orchestrator] Rootless mapping complete: host user -> container root");
     Ok(())
 }
+
+// ============================================================================
+// JOINING AN EXISTING CONTAINER'S NAMESPACES (`bento exec`)
+// ============================================================================
+
+/// Namespaces [`join_namespaces`] joins, in the order `setns(2)` needs them:
+/// user first (so it still has the privilege to join the rest), then pid
+/// (so a *subsequent fork* lands in the target's pid namespace - joining
+/// pid here doesn't move the calling process itself), then the remainder.
+const JOIN_ORDER: &[(&str, CloneFlags)] = &[
+    ("user", CloneFlags::CLONE_NEWUSER),
+    ("pid", CloneFlags::CLONE_NEWPID),
+    ("mnt", CloneFlags::CLONE_NEWNS),
+    ("net", CloneFlags::CLONE_NEWNET),
+    ("ipc", CloneFlags::CLONE_NEWIPC),
+    ("uts", CloneFlags::CLONE_NEWUTS),
+    ("cgroup", CloneFlags::CLONE_NEWCGROUP),
+];
+
+/// Joins every namespace of `target_pid` that's still present under
+/// `/proc/<pid>/ns/`, for `bento exec`'s "tenant" path. Must be called
+/// before forking the process that will actually run the requested
+/// command, since a joined pid namespace only takes effect for children
+/// created afterwards.
+pub fn join_namespaces(target_pid: Pid) -> Result<()> {
+    for (name, flag) in JOIN_ORDER {
+        let path = format!("/proc/{target_pid}/ns/{name}");
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(_) => continue, // not present on this kernel/process - skip it
+        };
+        setns(&file, *flag).map_err(|e| anyhow!("Failed to join {name} namespace: {e}"))?;
+        println!("[Exec] Joined {name} namespace of PID {target_pid}");
+    }
+    Ok(())
+}
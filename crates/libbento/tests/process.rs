@@ -0,0 +1,56 @@
+use libbento::process::ContainerStatus;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_can_start_only_from_created() {
+        assert!(ContainerStatus::Created.can_start());
+        assert!(!ContainerStatus::Creating.can_start());
+        assert!(!ContainerStatus::Running.can_start());
+        assert!(!ContainerStatus::Paused.can_start());
+        assert!(!ContainerStatus::Stopped.can_start());
+    }
+
+    #[test]
+    fn test_can_kill_only_while_running_or_paused() {
+        assert!(ContainerStatus::Running.can_kill());
+        assert!(ContainerStatus::Paused.can_kill());
+        assert!(!ContainerStatus::Created.can_kill());
+        assert!(!ContainerStatus::Creating.can_kill());
+        assert!(!ContainerStatus::Stopped.can_kill());
+    }
+
+    #[test]
+    fn test_can_delete_only_once_stopped() {
+        assert!(ContainerStatus::Stopped.can_delete());
+        assert!(!ContainerStatus::Created.can_delete());
+        assert!(!ContainerStatus::Creating.can_delete());
+        assert!(!ContainerStatus::Running.can_delete());
+        assert!(!ContainerStatus::Paused.can_delete());
+    }
+
+    #[test]
+    fn test_can_pause_and_resume_are_inverse_of_each_other() {
+        assert!(ContainerStatus::Running.can_pause());
+        assert!(!ContainerStatus::Paused.can_pause());
+
+        assert!(ContainerStatus::Paused.can_resume());
+        assert!(!ContainerStatus::Running.can_resume());
+    }
+
+    #[test]
+    fn test_can_exec_only_while_running() {
+        assert!(ContainerStatus::Running.can_exec());
+        for status in [
+            ContainerStatus::Creating,
+            ContainerStatus::Created,
+            ContainerStatus::Paused,
+            ContainerStatus::Stopped,
+        ] {
+            assert!(!status.can_exec());
+        }
+    }
+
+}